@@ -0,0 +1,184 @@
+use dungeon_crawler::map::{
+    self,
+    generator::{Door, Role, Tile},
+};
+use rand::{prelude::StdRng, SeedableRng};
+use std::env;
+
+const PNG_TILE_SIZE: u32 = 32;
+const PNG_WALL_SIZE: u32 = 4;
+
+fn main() {
+    let seed = get_arg("--seed").unwrap_or(42312);
+    let grid_size = get_arg("--grid").unwrap_or(3);
+    let number_of_tiles = get_arg("--tiles").unwrap_or(25);
+    let floor = get_arg("--floor").unwrap_or(1);
+    let theme = map::get_theme(get_arg::<String>("--theme").unwrap_or_else(|| map::get_floor_theme(floor)).as_str());
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let tiles = map::generate_layout(&mut rng, floor, grid_size, number_of_tiles);
+
+    println!(
+        "Theme: {}, seed: {}, grid: {}, tiles: {}, layout: {}",
+        theme.name,
+        seed,
+        grid_size,
+        number_of_tiles,
+        map::layout_mode().unwrap_or_else(|| format!("floor {}", floor))
+    );
+
+    if let Err(err) = map::generator::validate(&tiles, grid_size, number_of_tiles) {
+        println!("Invalid layout: {}", err);
+    }
+
+    println!();
+    print_map(&tiles);
+    println!();
+    print_pieces(&tiles, grid_size, &theme);
+
+    let args: Vec<String> = env::args().collect();
+    if let Some(path) = args.iter().position(|a| a == "--png").and_then(|pos| args.get(pos + 1)) {
+        write_png(&tiles, path);
+        println!("\nWrote {}", path);
+    }
+}
+
+fn get_arg<T: std::str::FromStr>(name: &str) -> Option<T> {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|pos| args.get(pos + 1))
+        .and_then(|value| value.parse().ok())
+}
+
+fn role_symbol(role: Role) -> char {
+    match role {
+        Role::Start => 'S',
+        Role::Normal => '.',
+        Role::Treasure => 'T',
+        Role::Boss => 'B',
+        Role::Shrine => '+',
+        Role::Secret => '?',
+    }
+}
+
+fn role_color(role: Role) -> [u8; 3] {
+    match role {
+        Role::Start => [70, 160, 70],
+        Role::Normal => [150, 140, 120],
+        Role::Treasure => [230, 122, 0],
+        Role::Boss => [222, 22, 0],
+        Role::Shrine => [90, 140, 220],
+        Role::Secret => [120, 80, 160],
+    }
+}
+
+fn door_symbol(door: Door) -> char {
+    match door {
+        Door::None => ' ',
+        Door::Closed => 'D',
+        Door::Lever => 'L',
+        Door::Locked(lock) => std::char::from_digit(lock as u32, 10).unwrap_or('K'),
+    }
+}
+
+/// Every tile is drawn as a 3x3 block with its role in the middle and a gap
+/// in the wall for every entrance, doors show as `D`, lever doors as `L` and
+/// locked doors as the number of their key. Entrances are ordered -z, +x, +z, -x.
+fn print_map(tiles: &[Vec<Option<Tile>>]) {
+    for z in 0..tiles[0].len() {
+        let mut rows = [String::new(), String::new(), String::new()];
+
+        for column in tiles.iter() {
+            match &column[z] {
+                Some(tile) => {
+                    let wall = |entrance: usize, wall: char| {
+                        if tile.entrances[entrance] {
+                            door_symbol(tile.doors[entrance])
+                        } else {
+                            wall
+                        }
+                    };
+                    rows[0].extend(['#', wall(0, '#'), '#']);
+                    rows[1].extend([wall(3, '#'), role_symbol(tile.role), wall(1, '#')]);
+                    rows[2].extend(['#', wall(2, '#'), '#']);
+                }
+                None => rows.iter_mut().for_each(|row| row.push_str("   ")),
+            }
+        }
+
+        rows.iter().for_each(|row| println!("{}", row.trim_end()));
+    }
+}
+
+fn print_pieces(tiles: &[Vec<Option<Tile>>], grid_size: usize, theme: &map::Theme) {
+    println!(
+        "{:>8} {:>9} {:<20} {:>8}  {:<8} keys",
+        "tile", "entrances", "piece", "rotation", "role"
+    );
+
+    for (x, column) in tiles.iter().enumerate() {
+        for (z, tile) in column.iter().enumerate() {
+            if let Some(tile) = tile {
+                let (piece, rotation) = match theme.determine_tile(&tile.entrances) {
+                    Some((piece, rotation)) => (piece.model.as_str(), rotation),
+                    None => (theme.empty_tile.as_str(), 0.0),
+                };
+                let entrances: String = tile.entrances.iter().map(|e| if *e { '1' } else { '0' }).collect();
+
+                let keys: Vec<String> = tile.keys.iter().map(|key| key.to_string()).collect();
+
+                let line = format!(
+                    "{:>8} {:>9} {:<20} {:>8}  {:<8} {}",
+                    format!("{},{}", x as i32 - grid_size as i32, z as i32 - grid_size as i32),
+                    entrances,
+                    piece,
+                    rotation,
+                    format!("{:?}", tile.role),
+                    keys.join(",")
+                );
+                println!("{}", line.trim_end());
+            }
+        }
+    }
+}
+
+fn write_png(tiles: &[Vec<Option<Tile>>], path: &str) {
+    let size = PNG_TILE_SIZE;
+    let wall = PNG_WALL_SIZE;
+    let mut image = image::RgbImage::from_pixel(
+        tiles.len() as u32 * size,
+        tiles[0].len() as u32 * size,
+        image::Rgb([20, 20, 20]),
+    );
+
+    for (x, column) in tiles.iter().enumerate() {
+        for (z, tile) in column.iter().enumerate() {
+            if let Some(tile) = tile {
+                let color = role_color(tile.role);
+
+                for px in 0..size {
+                    for pz in 0..size {
+                        let is_door = (size / 3..size * 2 / 3).contains(&px) || (size / 3..size * 2 / 3).contains(&pz);
+                        let sides = [pz < wall, px >= size - wall, pz >= size - wall, px < wall];
+
+                        let pixel = match (0..4).find(|side| sides[*side]) {
+                            Some(side) if is_door && tile.entrances[side] => match tile.doors[side] {
+                                Door::None => color,
+                                Door::Closed => [140, 90, 40],
+                                Door::Lever => [150, 150, 170],
+                                Door::Locked(_) => [240, 200, 40],
+                            },
+                            Some(_) => [60, 50, 45],
+                            None => color,
+                        };
+                        image.put_pixel(x as u32 * size + px, z as u32 * size + pz, image::Rgb(pixel));
+                    }
+                }
+            }
+        }
+    }
+
+    image.save(path).unwrap_or_else(|err| panic!("Could not write {}: {}", path, err));
+}
@@ -4,8 +4,10 @@ pub const UPDATES_PER_SECOND: f32 = 50.0;
 pub const CAMERA_ROTATION: f32 = 45.0;
 pub const GRID_COUNT: i32 = 10;
 pub const GRID_DIST: f32 = 0.635;
+pub const TILE_SIZE: f32 = 14.0;
 pub const UI_TRANSITION_TIME: f32 = 0.6;
 
+pub const TEAM_NEUTRAL: usize = 0;
 pub const TEAM_FRIENDLY: usize = 1;
 pub const TEAM_HOSTILE: usize = 2;
 
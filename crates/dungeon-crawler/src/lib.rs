@@ -14,7 +14,7 @@ use winit::{
 use world::GameState;
 
 mod config;
-mod map;
+pub mod map;
 mod state;
 mod ui;
 mod utils;
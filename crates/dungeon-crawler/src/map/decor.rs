@@ -1,21 +1,171 @@
+use super::Role;
 use engine::file;
-use serde_derive::Deserialize;
+use rand::{prelude::StdRng, Rng};
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize)]
+fn default_weight() -> f32 {
+    1.0
+}
+
+fn is_default_weight(value: &f32) -> bool {
+    *value == 1.0
+}
+
+fn is_zero(value: &f32) -> bool {
+    *value == 0.0
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Alternative {
+    pub name: String,
+    #[serde(default = "default_weight", skip_serializing_if = "is_default_weight")]
+    pub weight: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Decor {
     pub name: String,
     pub pos: [i32; 2],
     pub rotation: f32,
     pub rotation_rng: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub light_radius: Option<f32>,
+    /// Random offset in grid cells added to both axes of `pos`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub jitter: f32,
+    /// Chance between 0 and 1 that the decor is placed at all.
+    #[serde(default = "default_weight", skip_serializing_if = "is_default_weight")]
+    pub chance: f32,
+    /// Weight of `name` when picking between it and the alternatives.
+    #[serde(default = "default_weight", skip_serializing_if = "is_default_weight")]
+    pub weight: f32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<Alternative>,
+    /// Entrances of the piece, ordered -z, +x, +z, -x, that must all have a door.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub doors: Vec<usize>,
+}
+
+/// A decor with its placement rules resolved, in tile space.
+#[derive(Clone, Debug)]
+pub struct Placement {
+    pub name: String,
+    pub pos: [f32; 2],
+    pub rotation: f32,
     pub light_radius: Option<f32>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl Decor {
+    pub fn new(name: &str, pos: [i32; 2], rotation: f32) -> Self {
+        Self {
+            name: name.into(),
+            pos,
+            rotation,
+            rotation_rng: 0.0,
+            light_radius: None,
+            jitter: 0.0,
+            chance: 1.0,
+            weight: 1.0,
+            alternatives: vec![],
+            doors: vec![],
+        }
+    }
+
+    /// `doors` are the entrances of this placement that have a door, in the orientation of the piece.
+    pub fn is_allowed(&self, doors: &[bool; 4]) -> bool {
+        self.doors.iter().all(|entrance| doors.get(*entrance).copied().unwrap_or(false))
+    }
+
+    /// The decor exactly as authored, without rolling any of the rules.
+    pub fn authored(&self) -> Placement {
+        Placement {
+            name: self.name.clone(),
+            pos: [self.pos[0] as f32, self.pos[1] as f32],
+            rotation: self.rotation,
+            light_radius: self.light_radius,
+        }
+    }
+
+    /// Rolls the placement rules, returns None when the decor should be left out.
+    pub fn place(&self, rng: &mut StdRng, doors: &[bool; 4]) -> Option<Placement> {
+        if !self.is_allowed(doors) || rng.gen::<f32>() >= self.chance {
+            return None;
+        }
+
+        let total: f32 = self.weight + self.alternatives.iter().map(|a| a.weight).sum::<f32>();
+        let mut roll = rng.gen::<f32>() * total - self.weight;
+        let mut name = &self.name;
+        for alternative in self.alternatives.iter() {
+            if roll < 0.0 {
+                break;
+            }
+
+            name = &alternative.name;
+            roll -= alternative.weight;
+        }
+
+        let mut jitter = || (rng.gen::<f32>() * 2.0 - 1.0) * self.jitter;
+        let pos = [self.pos[0] as f32 + jitter(), self.pos[1] as f32 + jitter()];
+        let rotation = self.rotation + (rng.gen::<f32>() * 2.0 - 1.0) * self.rotation_rng;
+
+        Some(Placement {
+            name: name.clone(),
+            pos,
+            rotation,
+            light_radius: self.light_radius,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HazardKind {
+    /// Hits once every time it triggers.
+    Spikes,
+    /// Burns while active.
+    Fire,
+    /// Like fire, but lingers in a green cloud.
+    Poison,
+}
+
+/// A trap on the tile floor. It runs in cycles of `interval` seconds: idle,
+/// then `warning` seconds of telegraph, then `active` seconds of damage.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HazardDefinition {
+    pub kind: HazardKind,
+    pub pos: [i32; 2],
+    /// Radius of the damaging area in grid cells.
+    pub radius: f32,
+    /// Damage per trigger for spikes, per second for fire and poison.
+    pub damage: f32,
+    pub interval: f32,
+    pub active: f32,
+    pub warning: f32,
+    /// Decor model shown for the hazard, spikes rise out of the floor when they trigger.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Decor whose particle emitters are reused, they only burn while the hazard does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emitter: Option<String>,
+    /// Played when the telegraph starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sound: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disarmable: bool,
+    #[serde(default = "default_weight", skip_serializing_if = "is_default_weight")]
+    pub chance: f32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct TileDecor {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<Role>,
     pub decor: Vec<Decor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hazards: Vec<HazardDefinition>,
 }
 
-pub fn get_decor(tile: &str, variant: usize) -> Vec<Decor> {
+pub fn get_tile_decor(tile: &str) -> Vec<TileDecor> {
     if tile.contains("empty") {
         return vec![];
     }
@@ -23,20 +173,64 @@ pub fn get_decor(tile: &str, variant: usize) -> Vec<Decor> {
     let path = format!("tiles/{}.json", tile);
 
     match serde_json::from_str::<Vec<TileDecor>>(file::read_string(&path).as_str()) {
-        Ok(variants) if !variants.is_empty() => variants[variant].decor.clone(),
-        Ok(_) => vec![],
+        Ok(variants) => variants,
         Err(err) => panic!("{}", err),
     }
 }
 
-pub fn get_variants_count(tile: &str) -> usize {
-    if tile.contains("empty") {
-        return 0;
+/// Writes the variants back to the tile file with four space indentation,
+/// keeping the line endings of the file it replaces so diffs stay small.
+pub fn save_tile_decor(tile: &str, variants: &[TileDecor]) -> String {
+    let path = format!("tiles/{}.json", tile);
+    let mut buffer = vec![];
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    serde::Serialize::serialize(variants, &mut serializer).unwrap_or_else(|err| panic!("Could not serialize {}: {}", path, err));
+
+    let mut json = String::from_utf8(buffer).expect("Serialized json is not utf-8!");
+    json.push('\n');
+
+    if file::exists(&path) && file::read_string(&path).contains("\r\n") {
+        json = json.replace('\n', "\r\n");
     }
 
-    let path = format!("tiles/{}.json", tile);
-    match serde_json::from_str::<Vec<TileDecor>>(file::read_string(&path).as_str()) {
-        Ok(variants) => variants.len(),
-        Err(err) => panic!("{}", err),
+    file::write_file(&format!("assets/{}", path), &json);
+    path
+}
+
+pub fn get_hazards(tile: &str, variant: usize) -> Vec<HazardDefinition> {
+    match get_tile_decor(tile).get(variant) {
+        Some(variant) => variant.hazards.clone(),
+        None => vec![],
+    }
+}
+
+pub fn get_decor(tile: &str, variant: usize) -> Vec<Decor> {
+    match get_tile_decor(tile).get(variant) {
+        Some(variant) => variant.decor.clone(),
+        None => vec![],
+    }
+}
+
+/// Returns the variants made for the given role, falling back to the
+/// variants without any role when the tile has none for it.
+pub fn get_variants(tile: &str, role: Role) -> Vec<usize> {
+    let variants = get_tile_decor(tile);
+    if variants.is_empty() {
+        return vec![];
+    }
+
+    let with_role = |role: Role| -> Vec<usize> {
+        variants
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.roles.contains(&role) || (role == Role::Normal && v.roles.is_empty()))
+            .map(|(index, _)| index)
+            .collect()
+    };
+
+    match with_role(role) {
+        matching if !matching.is_empty() => matching,
+        _ => with_role(Role::Normal),
     }
 }
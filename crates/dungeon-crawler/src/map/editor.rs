@@ -0,0 +1,189 @@
+use super::{
+    decor::{self, Decor, Placement, TileDecor},
+    Theme,
+};
+
+const ROTATION_STEP: f32 = 45.0;
+
+/// State of the `--edit` mode, edits the decor variants of a single tile
+/// and writes them back to the tile file.
+pub struct Editor {
+    pub piece: String,
+    pub path: String,
+    pub variants: Vec<TileDecor>,
+    pub variant: usize,
+    pub catalogue: Vec<String>,
+    pub brush: usize,
+    pub rotation: f32,
+    pub cursor: Option<[i32; 2]>,
+    pub selected: Option<usize>,
+    pub status: String,
+    pub unsaved: bool,
+    pub rebuild: bool,
+}
+
+impl Editor {
+    pub fn new(theme: &Theme, piece: &str, variant: usize, catalogue: Vec<String>) -> Self {
+        let path = theme.decor_path(theme.get_piece(piece).unwrap_or_else(|| panic!("Could not find tile: {}!", piece)));
+
+        let mut variants = decor::get_tile_decor(&path);
+        if variants.is_empty() {
+            variants.push(TileDecor::default());
+        }
+
+        Self {
+            piece: piece.into(),
+            path,
+            variant: variant.min(variants.len() - 1),
+            variants,
+            catalogue,
+            brush: 0,
+            rotation: 0.0,
+            cursor: None,
+            selected: None,
+            status: String::new(),
+            unsaved: false,
+            rebuild: true,
+        }
+    }
+
+    pub fn decor(&self) -> &[Decor] {
+        &self.variants[self.variant].decor
+    }
+
+    pub fn brush_name(&self) -> &str {
+        self.catalogue.get(self.brush).map_or("", |name| name.as_str())
+    }
+
+    /// The current variant as authored, plus the brush under the cursor when it points at a free cell.
+    pub fn preview(&self) -> Vec<Placement> {
+        let mut preview: Vec<Placement> = self.decor().iter().map(|d| d.authored()).collect();
+
+        if let Some(cursor) = self.cursor {
+            if self.find(cursor).is_none() && !self.catalogue.is_empty() {
+                preview.push(Decor::new(self.brush_name(), cursor, self.rotation).authored());
+            }
+        }
+
+        preview
+    }
+
+    pub fn hover(&mut self, cell: Option<[i32; 2]>) {
+        if self.cursor != cell {
+            self.cursor = cell;
+            self.rebuild = true;
+        }
+    }
+
+    /// Selects the decor under the cursor, or places the brush when the cell is free.
+    pub fn click(&mut self) {
+        let cell = match self.cursor {
+            Some(cell) => cell,
+            None => return,
+        };
+
+        match self.find(cell) {
+            Some(index) if self.selected == Some(index) => self.selected = None,
+            Some(index) => {
+                let decor = &self.variants[self.variant].decor[index];
+                if let Some(brush) = self.catalogue.iter().position(|name| *name == decor.name) {
+                    self.brush = brush;
+                }
+
+                self.rotation = decor.rotation;
+                self.selected = Some(index);
+            }
+            None if !self.catalogue.is_empty() => {
+                let decor = Decor::new(self.brush_name(), cell, self.rotation);
+                self.variants[self.variant].decor.push(decor);
+                self.selected = Some(self.decor().len() - 1);
+                self.edited();
+            }
+            None => {}
+        }
+
+        self.rebuild = true;
+    }
+
+    /// Steps through the catalogue, the selected decor is swapped along with the brush.
+    pub fn cycle_brush(&mut self, step: i32) {
+        if self.catalogue.is_empty() {
+            return;
+        }
+
+        self.brush = (self.brush as i32 + step).rem_euclid(self.catalogue.len() as i32) as usize;
+        let name = self.brush_name().to_string();
+
+        if let Some(decor) = self.selected_mut() {
+            decor.name = name;
+            self.edited();
+        }
+
+        self.rebuild = true;
+    }
+
+    pub fn rotate(&mut self) {
+        self.rotation += ROTATION_STEP;
+        if self.rotation > 180.0 {
+            self.rotation -= 360.0;
+        }
+
+        let rotation = self.rotation;
+        if let Some(decor) = self.selected_mut() {
+            decor.rotation = rotation;
+            self.edited();
+        }
+
+        self.rebuild = true;
+    }
+
+    pub fn delete(&mut self) {
+        if let Some(index) = self.selected.take() {
+            self.variants[self.variant].decor.remove(index);
+            self.edited();
+        }
+    }
+
+    pub fn cycle_variant(&mut self, step: i32) {
+        self.variant = (self.variant as i32 + step).rem_euclid(self.variants.len() as i32) as usize;
+        self.selected = None;
+        self.rebuild = true;
+    }
+
+    pub fn add_variant(&mut self) {
+        self.variants.push(TileDecor::default());
+        self.variant = self.variants.len() - 1;
+        self.selected = None;
+        self.edited();
+    }
+
+    pub fn duplicate_variant(&mut self) {
+        self.variants.push(self.variants[self.variant].clone());
+        self.variant = self.variants.len() - 1;
+        self.selected = None;
+        self.edited();
+    }
+
+    pub fn save(&mut self) {
+        let path = decor::save_tile_decor(&self.path, &self.variants);
+        self.status = format!("Saved {}", path);
+        self.unsaved = false;
+    }
+
+    fn find(&self, cell: [i32; 2]) -> Option<usize> {
+        self.decor().iter().position(|d| d.pos == cell)
+    }
+
+    fn selected_mut(&mut self) -> Option<&mut Decor> {
+        match self.selected {
+            Some(index) => self.variants[self.variant].decor.get_mut(index),
+            None => None,
+        }
+    }
+
+    fn edited(&mut self) {
+        self.status.clear();
+        self.unsaved = true;
+        self.rebuild = true;
+    }
+}
@@ -0,0 +1,116 @@
+use super::{connect, fit_tile_count, LayoutGenerator, Tile};
+use rand::{prelude::StdRng, Rng};
+
+/// Binary space partitioning: the grid is split into regions, every leaf gets a
+/// small room and sibling regions are joined by corridors.
+pub struct Bsp {
+    pub min_size: usize,
+    pub max_room_size: usize,
+}
+
+impl Default for Bsp {
+    fn default() -> Self {
+        Self {
+            min_size: 2,
+            max_room_size: 2,
+        }
+    }
+}
+
+impl LayoutGenerator for Bsp {
+    fn generate(&self, rng: &mut StdRng, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<Tile>>> {
+        let size = grid_size * 2;
+        let start = (grid_size, grid_size);
+        let mut tiles = vec![vec![None; size]; size];
+        tiles[start.0][start.1] = Some(Tile::default());
+
+        let rooms = self.split(rng, &mut tiles, (0, 0, size, size));
+        if let Some(room) = rooms.iter().min_by_key(|(x, z)| x.abs_diff(start.0) + z.abs_diff(start.1)) {
+            carve_corridor(rng, &mut tiles, start, *room);
+        }
+
+        fit_tile_count(rng, &mut tiles, grid_size, number_of_tiles);
+        tiles
+    }
+}
+
+impl Bsp {
+    /// Returns one tile of every room in the region so that they can be joined up.
+    fn split(&self, rng: &mut StdRng, tiles: &mut [Vec<Option<Tile>>], region: (usize, usize, usize, usize)) -> Vec<(usize, usize)> {
+        let (x, z, width, depth) = region;
+        let can_split_x = width >= self.min_size * 2;
+        let can_split_z = depth >= self.min_size * 2;
+
+        if !can_split_x && !can_split_z {
+            return vec![self.room(rng, tiles, region)];
+        }
+
+        let split_x = if can_split_x && can_split_z {
+            rng.gen::<bool>()
+        } else {
+            can_split_x
+        };
+        let (first, second) = if split_x {
+            let at = rng.gen_range(self.min_size..=(width - self.min_size));
+            ((x, z, at, depth), (x + at, z, width - at, depth))
+        } else {
+            let at = rng.gen_range(self.min_size..=(depth - self.min_size));
+            ((x, z, width, at), (x, z + at, width, depth - at))
+        };
+
+        let first = self.split(rng, tiles, first);
+        let second = self.split(rng, tiles, second);
+
+        let from = first[rng.gen_range(0..first.len())];
+        let to = second[rng.gen_range(0..second.len())];
+        carve_corridor(rng, tiles, from, to);
+
+        first.into_iter().chain(second).collect()
+    }
+
+    fn room(
+        &self,
+        rng: &mut StdRng,
+        tiles: &mut [Vec<Option<Tile>>],
+        (x, z, width, depth): (usize, usize, usize, usize),
+    ) -> (usize, usize) {
+        let room_width = rng.gen_range(1..=self.max_room_size.min(width));
+        let room_depth = rng.gen_range(1..=self.max_room_size.min(depth));
+        let room_x = x + rng.gen_range(0..=(width - room_width));
+        let room_z = z + rng.gen_range(0..=(depth - room_depth));
+
+        for tx in room_x..(room_x + room_width) {
+            for tz in room_z..(room_z + room_depth) {
+                tiles[tx][tz].get_or_insert_with(Tile::default);
+
+                if tx > room_x {
+                    connect(tiles, (tx - 1, tz), (tx, tz));
+                }
+
+                if tz > room_z {
+                    connect(tiles, (tx, tz - 1), (tx, tz));
+                }
+            }
+        }
+
+        (room_x + rng.gen_range(0..room_width), room_z + rng.gen_range(0..room_depth))
+    }
+}
+
+/// L-shaped corridor, randomly starting along either axis.
+fn carve_corridor(rng: &mut StdRng, tiles: &mut [Vec<Option<Tile>>], from: (usize, usize), to: (usize, usize)) {
+    let x_first = rng.gen::<bool>();
+    let mut position = from;
+
+    while position != to {
+        let step_x = position.0 != to.0 && (x_first || position.1 == to.1);
+        let next = if step_x {
+            (if to.0 > position.0 { position.0 + 1 } else { position.0 - 1 }, position.1)
+        } else {
+            (position.0, if to.1 > position.1 { position.1 + 1 } else { position.1 - 1 })
+        };
+
+        connect(tiles, position, next);
+        position = next;
+    }
+}
@@ -0,0 +1,157 @@
+use super::{neighbour, Door, Role, Tile};
+use rand::{prelude::StdRng, Rng};
+
+const DOOR_CHANCE: f32 = 0.25;
+const LOCKED_TREASURE_CHANCE: f32 = 0.5;
+const LEVER_CHANCE: f32 = 0.3;
+
+/// Hangs doors in the entrances. Treasure and boss rooms are always behind
+/// doors, the boss room and some treasure rooms are locked. Some of the other
+/// doors are operated by levers instead. Keys are handed
+/// out in lock order and key `n` is always placed in a tile that can be
+/// reached by opening locks below `n` only, so every lock can be opened.
+pub(super) fn add_doors(rng: &mut StdRng, tiles: &mut [Vec<Option<Tile>>], grid_size: usize) {
+    let mut locked_rooms = vec![];
+    for role in [Role::Treasure, Role::Boss] {
+        for (x, column) in tiles.iter().enumerate() {
+            for (z, tile) in column.iter().enumerate() {
+                let is_locked = match tile {
+                    Some(tile) if tile.role == role => role == Role::Boss || rng.gen::<f32>() < LOCKED_TREASURE_CHANCE,
+                    _ => false,
+                };
+
+                if is_locked {
+                    locked_rooms.push((x, z));
+                }
+            }
+        }
+    }
+
+    let lock = |position: (usize, usize)| locked_rooms.iter().position(|room| *room == position);
+    let is_guarded = |tiles: &[Vec<Option<Tile>>], (x, z): (usize, usize)| {
+        tiles[x][z]
+            .as_ref()
+            .is_some_and(|t| t.role == Role::Treasure || t.role == Role::Boss)
+    };
+
+    for x in 0..tiles.len() {
+        for z in 0..tiles[x].len() {
+            // Every door is shared by two tiles, it is decided from the -z/-x side only
+            for entrance in [1, 2] {
+                let open = tiles[x][z].as_ref().is_some_and(|t| t.entrances[entrance]);
+                let next = match neighbour(tiles, (x, z), entrance) {
+                    Some(next) if open => next,
+                    _ => continue,
+                };
+
+                let door = match lock((x, z)).into_iter().chain(lock(next)).min() {
+                    Some(lock) => Door::Locked(lock),
+                    None if is_guarded(tiles, (x, z)) || is_guarded(tiles, next) => Door::Closed,
+                    None if rng.gen::<f32>() < DOOR_CHANCE => match rng.gen::<f32>() < LEVER_CHANCE {
+                        true => Door::Lever,
+                        false => Door::Closed,
+                    },
+                    None => Door::None,
+                };
+
+                set_door(tiles, (x, z), entrance, door);
+                set_door(tiles, next, (entrance + 2) % 4, door);
+            }
+        }
+    }
+
+    let start = (grid_size, grid_size);
+    for lock in 0..locked_rooms.len() {
+        let reachable = reachable_with_keys(tiles, start, lock);
+
+        // Prefer the far half of what can be reached, the start when nothing else is left
+        let candidates = &reachable[(reachable.len() / 2).max(1).min(reachable.len() - 1)..];
+        let (x, z) = candidates[rng.gen_range(0..candidates.len())];
+
+        if let Some(tile) = &mut tiles[x][z] {
+            tile.keys.push(lock);
+        }
+    }
+}
+
+/// Doors have to match between neighbours and every key has to be reachable
+/// while only holding the keys with lower numbers.
+pub(super) fn validate_doors(tiles: &[Vec<Option<Tile>>], grid_size: usize) -> Result<(), String> {
+    let mut locks = vec![];
+
+    for (x, column) in tiles.iter().enumerate() {
+        for (z, tile) in column.iter().enumerate() {
+            let tile = match tile {
+                Some(tile) => tile,
+                None => continue,
+            };
+
+            for entrance in 0..4 {
+                let door = tile.doors[entrance];
+                if door != Door::None && !tile.entrances[entrance] {
+                    return Err(format!("Door {} of tile {},{} has no entrance", entrance, x, z));
+                }
+
+                let opposite = neighbour(tiles, (x, z), entrance)
+                    .and_then(|(nx, nz)| tiles[nx][nz].as_ref())
+                    .map_or(Door::None, |t| t.doors[(entrance + 2) % 4]);
+
+                if door != opposite {
+                    return Err(format!("Door {} of tile {},{} does not match its neighbour", entrance, x, z));
+                }
+
+                if let Door::Locked(lock) = door {
+                    locks.push(lock);
+                }
+            }
+        }
+    }
+
+    locks.sort();
+    locks.dedup();
+
+    for lock in locks {
+        let has_key = reachable_with_keys(tiles, (grid_size, grid_size), lock)
+            .into_iter()
+            .any(|(x, z)| tiles[x][z].as_ref().is_some_and(|t| t.keys.contains(&lock)));
+
+        if !has_key {
+            return Err(format!("Key {} can not be reached without opening its own lock", lock));
+        }
+    }
+
+    Ok(())
+}
+
+/// Breadth first walk that only passes locked doors opened by the keys below `keys`.
+fn reachable_with_keys(tiles: &[Vec<Option<Tile>>], start: (usize, usize), keys: usize) -> Vec<(usize, usize)> {
+    let mut visited = vec![start];
+    let mut index = 0;
+
+    while index < visited.len() {
+        let position = visited[index];
+        index += 1;
+
+        if let Some(tile) = &tiles[position.0][position.1] {
+            for entrance in (0..4).filter(|e| tile.entrances[*e]) {
+                if matches!(tile.doors[entrance], Door::Locked(lock) if lock >= keys) {
+                    continue;
+                }
+
+                if let Some(next) = neighbour(tiles, position, entrance) {
+                    if tiles[next.0][next.1].is_some() && !visited.contains(&next) {
+                        visited.push(next);
+                    }
+                }
+            }
+        }
+    }
+
+    visited
+}
+
+fn set_door(tiles: &mut [Vec<Option<Tile>>], (x, z): (usize, usize), entrance: usize, door: Door) {
+    if let Some(tile) = &mut tiles[x][z] {
+        tile.doors[entrance] = door;
+    }
+}
@@ -0,0 +1,86 @@
+use super::{connect, distance, fit_tile_count, neighbour, LayoutGenerator, Tile, Walker};
+use rand::{prelude::StdRng, Rng};
+
+/// Wraps another layout and bridges tiles that are close on the grid but far
+/// apart along the paths, so the dungeon is no longer a tree.
+pub struct Loops {
+    pub layout: Box<dyn LayoutGenerator>,
+    pub loops: usize,
+    pub min_distance: usize,
+}
+
+impl Default for Loops {
+    fn default() -> Self {
+        Self {
+            layout: Box::<Walker>::default(),
+            loops: 3,
+            min_distance: 4,
+        }
+    }
+}
+
+impl LayoutGenerator for Loops {
+    fn generate(&self, rng: &mut StdRng, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<Tile>>> {
+        let mut tiles = self
+            .layout
+            .generate(rng, grid_size, number_of_tiles.saturating_sub(self.loops).max(1));
+
+        for _ in 0..self.loops {
+            let bridges = self.find_bridges(&tiles);
+            if bridges.is_empty() {
+                break;
+            }
+
+            let bridge = &bridges[rng.gen_range(0..bridges.len())];
+            for pair in bridge.windows(2) {
+                connect(&mut tiles, pair[0], pair[1]);
+            }
+        }
+
+        fit_tile_count(rng, &mut tiles, grid_size, number_of_tiles);
+        tiles
+    }
+}
+
+impl Loops {
+    /// Either two neighbouring tiles without an entrance between them, or an
+    /// empty position that would join two tiles.
+    fn find_bridges(&self, tiles: &[Vec<Option<Tile>>]) -> Vec<Vec<(usize, usize)>> {
+        let is_far = |a: (usize, usize), b: (usize, usize)| distance(tiles, a, b).is_some_and(|d| d >= self.min_distance);
+        let mut bridges = vec![];
+
+        for x in 0..tiles.len() {
+            for z in 0..tiles[x].len() {
+                let position = (x, z);
+
+                match &tiles[x][z] {
+                    Some(tile) => {
+                        for entrance in [1, 2] {
+                            if let Some(next) = neighbour(tiles, position, entrance) {
+                                if tiles[next.0][next.1].is_some() && !tile.entrances[entrance] && is_far(position, next) {
+                                    bridges.push(vec![position, next]);
+                                }
+                            }
+                        }
+                    }
+                    None => {
+                        let around: Vec<(usize, usize)> = (0..4)
+                            .filter_map(|entrance| neighbour(tiles, position, entrance))
+                            .filter(|(nx, nz)| tiles[*nx][*nz].is_some())
+                            .collect();
+
+                        for (index, a) in around.iter().enumerate() {
+                            for b in around.iter().skip(index + 1) {
+                                if is_far(*a, *b) {
+                                    bridges.push(vec![*a, position, *b]);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        bridges
+    }
+}
@@ -0,0 +1,394 @@
+use rand::{prelude::StdRng, Rng};
+use serde_derive::{Deserialize, Serialize};
+mod bsp;
+mod doors;
+mod loops;
+mod walker;
+pub use bsp::Bsp;
+pub use loops::Loops;
+pub use walker::Walker;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Start,
+    #[default]
+    Normal,
+    Treasure,
+    Boss,
+    Shrine,
+    Secret,
+}
+
+impl Role {
+    /// Upper bound of hostiles spawned in a room with this role.
+    pub fn hostile_budget(&self) -> f32 {
+        match self {
+            Role::Normal => 4.0,
+            Role::Treasure => 3.0,
+            Role::Secret => 2.0,
+            Role::Start | Role::Boss | Role::Shrine => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Door {
+    #[default]
+    None,
+    Closed,
+    /// Operated by the levers placed next to it on both sides.
+    Lever,
+    /// Opens with the key of the same number.
+    Locked(usize),
+}
+
+#[derive(Clone, Default)]
+pub struct Tile {
+    pub entrances: [bool; 4],
+    pub role: Role,
+    pub doors: [Door; 4],
+    pub keys: Vec<usize>,
+}
+
+/// Produces the entrance grid of a dungeon floor. The starting tile sits at
+/// `[grid_size][grid_size]` and every tile has to be reachable from it.
+pub trait LayoutGenerator {
+    fn generate(&self, rng: &mut StdRng, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<Tile>>>;
+}
+
+const OFFSETS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+pub fn generate(layout: &dyn LayoutGenerator, rng: &mut StdRng, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<Tile>>> {
+    let mut tiles = layout.generate(rng, grid_size, number_of_tiles);
+    if let Err(err) = validate(&tiles, grid_size, number_of_tiles) {
+        println!("Invalid layout: {}", err);
+    }
+
+    add_roles(&mut tiles, grid_size);
+    doors::add_doors(rng, &mut tiles, grid_size);
+    if let Err(err) = validate(&tiles, grid_size, number_of_tiles) {
+        println!("Invalid doors: {}", err);
+    }
+
+    tiles
+}
+
+pub fn get_layout(name: &str) -> Box<dyn LayoutGenerator> {
+    match name {
+        "walker" => Box::<Walker>::default(),
+        "bsp" => Box::<Bsp>::default(),
+        "loops" => Box::<Loops>::default(),
+        _ => panic!("Unknown layout: {}!", name),
+    }
+}
+
+/// Deeper floors move from the plain walker to layouts with more structure.
+pub fn get_floor_layout(floor: usize) -> Box<dyn LayoutGenerator> {
+    match floor.saturating_sub(1) % 3 {
+        0 => get_layout("walker"),
+        1 => get_layout("loops"),
+        _ => get_layout("bsp"),
+    }
+}
+
+/// Checks the invariants every layout has to hold: the expected number of tiles,
+/// entrances that match up between neighbours, every tile reachable from the start
+/// and every locked door opened by a key that can be fetched before it.
+pub fn validate(tiles: &[Vec<Option<Tile>>], grid_size: usize, number_of_tiles: usize) -> Result<(), String> {
+    if tiles[grid_size][grid_size].is_none() {
+        return Err("Missing starting tile".into());
+    }
+
+    let count = count_tiles(tiles);
+    if count != number_of_tiles {
+        return Err(format!("Expected {} tiles, got {}", number_of_tiles, count));
+    }
+
+    for x in 0..tiles.len() {
+        for z in 0..tiles[x].len() {
+            for entrance in 0..4 {
+                let open = tiles[x][z].as_ref().is_some_and(|t| t.entrances[entrance]);
+                let opposite = match neighbour(tiles, (x, z), entrance) {
+                    Some((nx, nz)) => tiles[nx][nz].as_ref().is_some_and(|t| t.entrances[(entrance + 2) % 4]),
+                    None => false,
+                };
+
+                if open != opposite {
+                    return Err(format!(
+                        "Entrance {} of tile {},{} does not match its neighbour",
+                        entrance, x, z
+                    ));
+                }
+            }
+        }
+    }
+
+    let reachable = reachable(tiles, (grid_size, grid_size)).len();
+    if reachable != count {
+        return Err(format!("Only {} of {} tiles are reachable", reachable, count));
+    }
+
+    doors::validate_doors(tiles, grid_size)
+}
+
+fn neighbour(tiles: &[Vec<Option<Tile>>], (x, z): (usize, usize), entrance: usize) -> Option<(usize, usize)> {
+    let (ox, oz) = OFFSETS[entrance];
+    let (nx, nz) = (x as i32 + ox, z as i32 + oz);
+
+    if nx >= 0 && nz >= 0 && (nx as usize) < tiles.len() && (nz as usize) < tiles[0].len() {
+        Some((nx as usize, nz as usize))
+    } else {
+        None
+    }
+}
+
+fn count_tiles(tiles: &[Vec<Option<Tile>>]) -> usize {
+    tiles.iter().flatten().filter(|t| t.is_some()).count()
+}
+
+/// Opens the entrances between two neighbouring positions, placing tiles where needed.
+fn connect(tiles: &mut [Vec<Option<Tile>>], a: (usize, usize), b: (usize, usize)) {
+    if let Some(entrance) = (0..4).find(|entrance| neighbour(tiles, a, *entrance) == Some(b)) {
+        tiles[a.0][a.1].get_or_insert_with(Tile::default).entrances[entrance] = true;
+        tiles[b.0][b.1].get_or_insert_with(Tile::default).entrances[(entrance + 2) % 4] = true;
+    }
+}
+
+fn remove_tile(tiles: &mut [Vec<Option<Tile>>], position: (usize, usize)) {
+    tiles[position.0][position.1] = None;
+
+    for entrance in 0..4 {
+        if let Some((nx, nz)) = neighbour(tiles, position, entrance) {
+            if let Some(tile) = &mut tiles[nx][nz] {
+                tile.entrances[(entrance + 2) % 4] = false;
+            }
+        }
+    }
+}
+
+/// Number of steps between two tiles when walking through open entrances.
+fn distance(tiles: &[Vec<Option<Tile>>], from: (usize, usize), to: (usize, usize)) -> Option<usize> {
+    let mut distances = vec![vec![usize::MAX; tiles[0].len()]; tiles.len()];
+    let mut queue = std::collections::VecDeque::from([from]);
+    distances[from.0][from.1] = 0;
+
+    while let Some(position) = queue.pop_front() {
+        if position == to {
+            return Some(distances[to.0][to.1]);
+        }
+
+        if let Some(tile) = &tiles[position.0][position.1] {
+            for entrance in (0..4).filter(|e| tile.entrances[*e]) {
+                if let Some((nx, nz)) = neighbour(tiles, position, entrance) {
+                    if distances[nx][nz] == usize::MAX {
+                        distances[nx][nz] = distances[position.0][position.1] + 1;
+                        queue.push_back((nx, nz));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Breadth first walk through the open entrances, nearest tiles first.
+fn reachable(tiles: &[Vec<Option<Tile>>], start: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut visited = vec![start];
+    let mut index = 0;
+
+    while index < visited.len() {
+        let position = visited[index];
+        index += 1;
+
+        if let Some(tile) = &tiles[position.0][position.1] {
+            for entrance in (0..4).filter(|e| tile.entrances[*e]) {
+                if let Some(next) = neighbour(tiles, position, entrance) {
+                    if tiles[next.0][next.1].is_some() && !visited.contains(&next) {
+                        visited.push(next);
+                    }
+                }
+            }
+        }
+    }
+
+    visited
+}
+
+/// Grows or trims a connected layout until it holds exactly `number_of_tiles`.
+/// Trimming only removes tiles that keep the rest connected, dead ends first.
+fn fit_tile_count(rng: &mut StdRng, tiles: &mut [Vec<Option<Tile>>], grid_size: usize, number_of_tiles: usize) {
+    let start = (grid_size, grid_size);
+
+    while count_tiles(tiles) > number_of_tiles {
+        let mut candidates: Vec<((usize, usize), usize)> = reachable(tiles, start)
+            .into_iter()
+            .skip(1)
+            .filter_map(|position| {
+                let mut trimmed = tiles.to_vec();
+                remove_tile(&mut trimmed, position);
+
+                let entrances = tiles[position.0][position.1].as_ref()?.entrances.iter().filter(|e| **e).count();
+                (reachable(&trimmed, start).len() == count_tiles(&trimmed)).then_some((position, entrances))
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            break;
+        }
+
+        let fewest = candidates.iter().map(|(_, entrances)| *entrances).min().unwrap_or(0);
+        candidates.retain(|(_, entrances)| *entrances == fewest);
+        let (position, _) = candidates[rng.gen_range(0..candidates.len())];
+        remove_tile(tiles, position);
+    }
+
+    while count_tiles(tiles) < number_of_tiles {
+        let options: Vec<((usize, usize), (usize, usize))> = reachable(tiles, start)
+            .into_iter()
+            .flat_map(|position| (0..4).map(move |entrance| (position, entrance)))
+            .filter_map(|(position, entrance)| {
+                let next = neighbour(tiles, position, entrance)?;
+                tiles[next.0][next.1].is_none().then_some((position, next))
+            })
+            .collect();
+
+        if options.is_empty() {
+            break;
+        }
+
+        let (position, next) = options[rng.gen_range(0..options.len())];
+        connect(tiles, position, next);
+    }
+}
+
+/// Walks the entrances from the starting tile and assigns room roles by distance:
+/// the boss waits in the furthest room, dead ends hold treasure and secrets and
+/// a shrine sits roughly halfway along the way.
+fn add_roles(tiles: &mut [Vec<Option<Tile>>], grid_size: usize) {
+    let mut distances = vec![vec![usize::MAX; grid_size * 2]; grid_size * 2];
+    let mut queue = std::collections::VecDeque::from([(grid_size, grid_size)]);
+    let mut rooms = vec![];
+    distances[grid_size][grid_size] = 0;
+
+    while let Some((x, z)) = queue.pop_front() {
+        rooms.push((x, z));
+
+        if let Some(tile) = &tiles[x][z] {
+            for entrance in (0..4).filter(|e| tile.entrances[*e]) {
+                if let Some((nx, nz)) = neighbour(tiles, (x, z), entrance) {
+                    if distances[nx][nz] == usize::MAX {
+                        distances[nx][nz] = distances[x][z] + 1;
+                        queue.push_back((nx, nz));
+                    }
+                }
+            }
+        }
+    }
+
+    // Rooms are visited breadth first, so the furthest room comes last
+    let start = rooms.remove(0);
+    set_role(tiles, start, Role::Start);
+
+    let boss = match rooms.pop() {
+        Some(boss) => boss,
+        None => return,
+    };
+    set_role(tiles, boss, Role::Boss);
+
+    let max_distance = distances[boss.0][boss.1];
+    let (dead_ends, others): (Vec<(usize, usize)>, Vec<(usize, usize)>) = rooms.into_iter().partition(|(x, z)| {
+        tiles[*x][*z]
+            .as_ref()
+            .is_some_and(|t| t.entrances.iter().filter(|e| **e).count() == 1)
+    });
+
+    for (index, room) in dead_ends.into_iter().rev().enumerate() {
+        match index {
+            0..=1 => set_role(tiles, room, Role::Treasure),
+            2..=3 => set_role(tiles, room, Role::Secret),
+            _ => {}
+        }
+    }
+
+    if let Some(shrine) = others
+        .into_iter()
+        .min_by_key(|(x, z)| (distances[*x][*z] as i32 - max_distance as i32 / 2).abs())
+    {
+        set_role(tiles, shrine, Role::Shrine);
+    }
+}
+
+fn set_role(tiles: &mut [Vec<Option<Tile>>], (x, z): (usize, usize), role: Role) {
+    if let Some(tile) = &mut tiles[x][z] {
+        tile.role = role;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    const GRID_SIZE: usize = 3;
+    const NUMBER_OF_TILES: usize = 25;
+    const SEEDS: std::ops::Range<u64> = 0..16;
+
+    fn assert_valid(name: &str, layout: &dyn LayoutGenerator) {
+        for seed in SEEDS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let tiles = layout.generate(&mut rng, GRID_SIZE, NUMBER_OF_TILES);
+            assert_eq!(
+                validate(&tiles, GRID_SIZE, NUMBER_OF_TILES),
+                Ok(()),
+                "{} layout, seed {}",
+                name,
+                seed
+            );
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            let tiles = generate(layout, &mut rng, GRID_SIZE, NUMBER_OF_TILES);
+            assert_eq!(
+                validate(&tiles, GRID_SIZE, NUMBER_OF_TILES),
+                Ok(()),
+                "{} with doors, seed {}",
+                name,
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn walker_is_valid() {
+        assert_valid("walker", &Walker::default());
+    }
+
+    #[test]
+    fn bsp_is_valid() {
+        assert_valid("bsp", &Bsp::default());
+    }
+
+    #[test]
+    fn loops_is_valid() {
+        assert_valid("loops", &Loops::default());
+    }
+
+    #[test]
+    fn floor_layouts_are_valid() {
+        for floor in 1..=6 {
+            assert_valid(&format!("floor {}", floor), get_floor_layout(floor).as_ref());
+        }
+    }
+
+    #[test]
+    fn validate_rejects_one_sided_entrance() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut tiles = Walker::default().generate(&mut rng, GRID_SIZE, NUMBER_OF_TILES);
+        let start = tiles[GRID_SIZE][GRID_SIZE].as_mut().unwrap();
+        let open = (0..4).find(|e| start.entrances[*e]).unwrap();
+        start.entrances[open] = false;
+
+        assert!(validate(&tiles, GRID_SIZE, NUMBER_OF_TILES).is_err());
+    }
+}
@@ -1,44 +1,51 @@
+use super::{LayoutGenerator, Tile};
 use cgmath::*;
 use rand::{prelude::StdRng, Rng};
 
-#[derive(Clone, Default)]
-pub struct Tile {
-    pub entrances: [bool; 4],
+/// Random walk that grows the dungeon one tile at a time, preferring
+/// positions with few neighbours early on so the layout branches out.
+pub struct Walker {
+    pub random_compare_start: f32,
+    pub random_compare_end: f32,
 }
 
-pub fn generate(rng: &mut StdRng, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<Tile>>> {
-    let mut tiles = vec![vec![None; grid_size * 2]; grid_size * 2];
-    let mut taken_positions = vec![(0, 0)];
+impl Default for Walker {
+    fn default() -> Self {
+        Self {
+            random_compare_start: 0.2,
+            random_compare_end: 0.01,
+        }
+    }
+}
 
-    tiles[grid_size][grid_size] = Some(Tile {
-        entrances: [false, false, false, false],
-    });
+impl LayoutGenerator for Walker {
+    fn generate(&self, rng: &mut StdRng, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<Tile>>> {
+        let mut tiles = vec![vec![None; grid_size * 2]; grid_size * 2];
+        let mut taken_positions = vec![(0, 0)];
 
-    let random_compare_start = 0.2f32;
-    let random_compare_end = 0.01f32;
+        tiles[grid_size][grid_size] = Some(Tile::default());
 
-    for i in 0..(number_of_tiles - 1) {
-        let random_perc = (i as f32) / (number_of_tiles - 1) as f32;
-        let random_compare = vec1(random_compare_start).lerp(vec1(random_compare_end), random_perc).x;
-        let mut check_pos = new_position(grid_size, rng, &taken_positions);
+        for i in 0..(number_of_tiles - 1) {
+            let random_perc = (i as f32) / (number_of_tiles - 1) as f32;
+            let random_compare = vec1(self.random_compare_start).lerp(vec1(self.random_compare_end), random_perc).x;
+            let mut check_pos = new_position(grid_size, rng, &taken_positions);
 
-        if number_of_neighbors(&check_pos, &taken_positions) > 1 && rng.gen::<f32>() > random_compare * 0.5 {
-            for _ in 0..100 {
-                check_pos = selective_new_position(grid_size, rng, &taken_positions);
-                if number_of_neighbors(&check_pos, &taken_positions) == 0 {
-                    break;
+            if number_of_neighbors(&check_pos, &taken_positions) > 1 && rng.gen::<f32>() > random_compare * 0.5 {
+                for _ in 0..100 {
+                    check_pos = selective_new_position(grid_size, rng, &taken_positions);
+                    if number_of_neighbors(&check_pos, &taken_positions) == 0 {
+                        break;
+                    }
                 }
             }
+            tiles[(check_pos.0 + grid_size as i32) as usize][(check_pos.1 + grid_size as i32) as usize] = Some(Tile::default());
+
+            taken_positions.insert(0, check_pos);
         }
-        tiles[(check_pos.0 + grid_size as i32) as usize][(check_pos.1 + grid_size as i32) as usize] = Some(Tile {
-            entrances: [false, false, false, false],
-        });
 
-        taken_positions.insert(0, check_pos);
+        add_entrances(&mut tiles, grid_size);
+        tiles
     }
-
-    add_entrances(&mut tiles, grid_size);
-    tiles
 }
 
 fn add_entrances(tiles: &mut [Vec<Option<Tile>>], grid_size: usize) {
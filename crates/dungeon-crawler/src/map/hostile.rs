@@ -0,0 +1,160 @@
+use engine::file;
+use serde_derive::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DamageType {
+    /// Reduced by armor.
+    #[default]
+    Physical,
+    Fire,
+    Poison,
+}
+
+/// Fraction of the damage of each type that is ignored, negative values make the damage hurt more.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Resistances {
+    pub physical: f32,
+    pub fire: f32,
+    pub poison: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Poison => self.poison,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeaponShape {
+    /// A circle of `radius` at `distance` in front of the wielder.
+    #[default]
+    Circle,
+    /// A swing `angle` degrees wide, out to the weapon's reach.
+    Arc,
+    /// A thrust out to the weapon's reach, twice `radius` wide.
+    Line,
+}
+
+/// One attack in a combo chain.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ComboStepDefinition {
+    pub animation: String,
+    /// Multiplies the weapon damage.
+    pub damage: f32,
+    /// Part of the step after which the hit lands.
+    pub activation: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WeaponDefinition {
+    pub damage: [f32; 2],
+    #[serde(default)]
+    pub damage_type: DamageType,
+    pub distance: f32,
+    pub radius: f32,
+    pub time: f32,
+    #[serde(default)]
+    pub shape: WeaponShape,
+    #[serde(default)]
+    pub angle: f32,
+    /// Targets hit by a single attack, nearest first, all of them when not set.
+    #[serde(default)]
+    pub max_targets: Option<usize>,
+    /// Whether walls between the wielder and a target block the hit.
+    #[serde(default = "default_line_of_sight")]
+    pub line_of_sight: bool,
+    /// Attacks in a row within the reset time go through these in order, a single plain attack when empty.
+    #[serde(default)]
+    pub combo: Vec<ComboStepDefinition>,
+    /// Distance the target is pushed away on a hit.
+    #[serde(default)]
+    pub knockback: f32,
+    /// Fixed steps both sides freeze for on a hit, for heavy weapons.
+    #[serde(default)]
+    pub hit_stop: u32,
+}
+
+fn default_line_of_sight() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BehaviourDefinition {
+    pub sight_range: f32,
+    pub leash_range: f32,
+    pub hearing_range: f32,
+    pub memory_time: f32,
+    pub alert_radius: f32,
+    pub alarm_sound: Option<String>,
+    pub idle_time: [f32; 2],
+    pub wander_radius: f32,
+    pub patrol_chance: f32,
+    pub patrol_points: usize,
+    pub patrol_radius: f32,
+    pub walk_speed: f32,
+    pub chase_speed: f32,
+    pub regeneration: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SummonDefinition {
+    pub hostile: String,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PhaseDefinition {
+    pub health: f32,
+    pub weapon: Option<WeaponDefinition>,
+    pub dexterity: Option<u32>,
+    pub chase_speed: Option<f32>,
+    pub summon: Option<SummonDefinition>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RewardDefinition {
+    pub experience: u32,
+    pub health: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BossDefinition {
+    pub phases: Vec<PhaseDefinition>,
+    pub reward: RewardDefinition,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HostileDefinition {
+    pub name: String,
+    pub model_file: String,
+    pub animation_graph: String,
+    pub model: String,
+    pub scale: f32,
+    pub strength: u32,
+    pub vitality: u32,
+    pub dexterity: u32,
+    pub level: u32,
+    #[serde(default)]
+    pub armor: f32,
+    #[serde(default)]
+    pub resistances: Resistances,
+    pub weapon: WeaponDefinition,
+    pub behaviour: BehaviourDefinition,
+    pub boss: Option<BossDefinition>,
+}
+
+pub fn get_hostile(name: &str) -> HostileDefinition {
+    let path = format!("hostiles/{}.json", name);
+
+    match serde_json::from_str::<HostileDefinition>(file::read_string(&path).as_str()) {
+        Ok(hostile) => hostile,
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -6,49 +6,121 @@ use bevy_ecs::{prelude::World, world::EntityMut};
 use cgmath::*;
 use engine::{
     collision::{Polygon, PolygonMethods},
+    ecs::components::AnimationGraph,
     Engine,
 };
 use rand::{prelude::StdRng, Rng, SeedableRng};
-use std::env;
+use std::{env, sync::Arc};
 mod decor;
-mod generator;
+mod editor;
+pub mod generator;
+mod hostile;
+mod theme;
+pub use decor::{HazardDefinition, HazardKind};
+pub use editor::Editor;
+pub use generator::Role;
+pub use hostile::{
+    BehaviourDefinition, ComboStepDefinition, DamageType, HostileDefinition, PhaseDefinition, Resistances, RewardDefinition,
+    WeaponDefinition, WeaponShape,
+};
+pub use theme::{
+    get_floor_theme, get_theme, BuffDefinition, ChestDefinition, DestructibleDefinition, LootDefinition, Piece, ShrineDefinition, Theme,
+};
+
+const LEVER_RADIUS: f32 = 1.0;
+const LEVER_WALL_DISTANCE: f32 = 0.65;
+const LEVER_DOOR_DISTANCE: f32 = 1.5;
+const CHEST_RADIUS: f32 = 1.2;
+const SHRINE_RADIUS: f32 = 1.5;
+
+struct HostileType {
+    key: String,
+    definition: HostileDefinition,
+    model: engine::model::GltfModel,
+    animation_graph: Arc<AnimationGraph>,
+}
 
 pub struct Map {
     seed: u64,
     tile_size: f32,
     grid_size: usize,
     number_of_tiles: usize,
+    pub floor: usize,
+    pub theme: Theme,
     tiles: engine::model::GltfModel,
     decor: engine::model::GltfModel,
-    hostiles: engine::model::GltfModel,
+    hostiles: Vec<HostileType>,
+    bosses: Vec<HostileType>,
     pub sound_effects: Vec<String>,
 }
 
 impl Map {
-    pub fn new(ctx: &engine::Context, seed: u64, grid_size: usize) -> Self {
-        let tiles = engine::load_model(ctx, "models/catacombs.glb");
-        let decor = engine::load_model(ctx, "models/decor.glb");
-        let hostiles = engine::load_model(ctx, "models/skeleton.glb");
+    pub fn new(ctx: &engine::Context, seed: u64, grid_size: usize, floor: usize) -> Self {
+        let theme = get_theme(&get_floor_theme(floor));
+        let tiles = engine::load_model(ctx, theme.tiles_model.as_str());
+        let decor = engine::load_model(ctx, theme.decor_model.as_str());
+        let load_hostiles = |names: &[String]| -> Vec<HostileType> {
+            names
+                .iter()
+                .map(|name| {
+                    let definition = hostile::get_hostile(name);
+                    let mut model = engine::load_model(ctx, definition.model_file.as_str());
+                    let animation_graph = Arc::new(AnimationGraph::load(&definition.animation_graph));
+                    model.add_bone_masks(animation_graph.layers().filter_map(|layer| layer.mask.as_deref()));
+                    HostileType {
+                        key: name.to_string(),
+                        definition,
+                        model,
+                        animation_graph,
+                    }
+                })
+                .collect()
+        };
+
+        let hostiles = load_hostiles(&theme.hostiles);
+        let bosses = load_hostiles(&theme.bosses);
+
+        // Hazard sounds are looked up in all tile variants of the theme up front
+        let hazard_sounds: Vec<String> = theme
+            .pieces
+            .iter()
+            .flat_map(|piece| decor::get_tile_decor(&theme.decor_path(piece)))
+            .flat_map(|variant| variant.hazards)
+            .filter_map(|hazard| hazard.sound)
+            .collect();
 
-        let sound_effects = hostiles.get_sound_effects();
+        let sound_effects = hostiles
+            .iter()
+            .chain(bosses.iter())
+            .flat_map(|h| {
+                h.model
+                    .get_sound_effects()
+                    .into_iter()
+                    .chain(h.definition.behaviour.alarm_sound.clone())
+            })
+            .chain(hazard_sounds)
+            .collect();
 
         let number_of_tiles = 25;
 
         Self {
-            tile_size: 14.0,
+            tile_size: config::TILE_SIZE,
             seed,
             grid_size,
             number_of_tiles,
+            floor,
+            theme,
             tiles,
             decor,
             hostiles,
+            bosses,
             sound_effects,
         }
     }
 
     pub fn generate(&mut self, world: &mut World, engine: &mut Engine) {
         let mut rng = StdRng::seed_from_u64(self.seed);
-        let mut tiles = generator::generate(&mut rng, self.grid_size, self.number_of_tiles);
+        let tiles = generate_layout(&mut rng, self.floor, self.grid_size, self.number_of_tiles);
         let gs_2 = self.grid_size * 2;
 
         (0..(gs_2 + 1)).for_each(|x| {
@@ -60,7 +132,7 @@ impl Map {
 
                 if x == gs_2 || z == gs_2 {
                     self.empty_tile(engine, &mut entity, center);
-                } else if let Some(t) = &mut tiles[x][z] {
+                } else if let Some(t) = &tiles[x][z] {
                     self.tile(engine, &mut entity, &mut rng, t, center);
                 } else {
                     self.empty_tile(engine, &mut entity, center);
@@ -69,15 +141,17 @@ impl Map {
         });
     }
 
-    pub fn single_tile(&mut self, engine: &mut engine::Engine, world: &mut World, tile_name: &str, variant: usize) {
+    pub fn single_tile(&self, engine: &mut engine::Engine, world: &mut World, tile_name: &str, decor: &[decor::Placement]) {
+        let piece = self
+            .theme
+            .get_piece(tile_name)
+            .unwrap_or_else(|| panic!("Could not find tile: {}!", tile_name));
+
         let mut entity = world.spawn_empty();
-        let collisions = self.tiles.collisions.get(tile_name).unwrap_or(&vec![]).clone();
-        let decor = decor::get_decor(format!("catacombs/{}", tile_name).as_str(), variant)
-            .iter()
-            .map(|d| self.add_decor(engine, d, Vector3::zero(), 0.0))
-            .collect();
+        let collisions = self.tiles.collisions.get(&piece.model).unwrap_or(&vec![]).clone();
+        let decor = decor.iter().map(|d| self.add_decor(engine, d, Vector3::zero(), 0.0)).collect();
 
-        let model = engine.initialize_model(&self.tiles, format!("tile-catacombs-{}", tile_name).as_str());
+        let model = engine.initialize_model(&self.tiles, piece.model.as_str());
         entity.insert(components::Tile::new(
             model,
             collisions,
@@ -87,26 +161,61 @@ impl Map {
             decor,
             vec![],
         ));
+    }
 
-        self.add_grid(world, Vector3::zero());
+    /// Names of the decor models that can be placed in a tile.
+    pub fn decor_catalogue(&self) -> Vec<String> {
+        let mut catalogue: Vec<String> = self
+            .decor
+            .meshes
+            .iter()
+            .map(|mesh| mesh.name.clone())
+            .filter(|name| !name.contains(['_', '.']))
+            .collect();
+
+        catalogue.sort();
+        catalogue.dedup();
+        catalogue
     }
 
     fn empty_tile(&self, engine: &mut engine::Engine, entity: &mut EntityMut, pos: Vector3<f32>) {
-        let model = engine.initialize_model(&self.tiles, "tile-empty");
+        let model = engine.initialize_model(&self.tiles, self.theme.empty_tile.as_str());
         entity.insert(components::Tile::new(model, vec![], pos, self.tile_size, 0.0, vec![], vec![]));
     }
 
-    fn tile(&self, engine: &mut engine::Engine, entity: &mut EntityMut, rng: &mut StdRng, tile: &mut generator::Tile, pos: Vector3<f32>) {
+    fn tile(&self, engine: &mut engine::Engine, entity: &mut EntityMut, rng: &mut StdRng, tile: &generator::Tile, pos: Vector3<f32>) {
         let entrances = tile.entrances;
-        let (t, rot) = determine_tile(&entrances);
-        let name = t.split('-').last().expect("Could not get map name!");
-
-        let tile = format!("catacombs/{}", name);
-        let variant = rng.gen_range(0..decor::get_variants_count(tile.as_str()));
+        let role = tile.role;
+        let doors = tile.doors;
+        let keys = tile.keys.clone();
+        let (piece, rot) = match self.theme.determine_tile(&entrances) {
+            Some(piece) => piece,
+            None => return self.empty_tile(engine, entity, pos),
+        };
+
+        let tile = self.theme.decor_path(piece);
+        let variants = decor::get_variants(tile.as_str(), role);
+        let variant = variants[rng.gen_range(0..variants.len())];
+
+        // Entrance `i` of the piece ends up as entrance `i + steps` once rotated
+        let steps = (rot / 90.0) as usize;
+        let piece_doors: [bool; 4] = std::array::from_fn(|entrance| doors[(entrance + steps) % 4] != generator::Door::None);
 
         let decor: Vec<components::Decor> = decor::get_decor(tile.as_str(), variant)
             .iter()
-            .map(|d| self.add_decor(engine, d, pos, rot))
+            .filter_map(|d| d.place(rng, &piece_doors))
+            .map(|d| self.add_decor(engine, &d, pos, rot))
+            .collect();
+
+        let hazards = decor::get_hazards(tile.as_str(), variant)
+            .into_iter()
+            .filter_map(|h| {
+                if rng.gen::<f32>() < h.chance {
+                    Some(self.add_hazard(rng, engine, h, pos, rot))
+                } else {
+                    None
+                }
+            })
             .collect();
 
         let decor_collisions: Vec<Polygon> = decor
@@ -121,38 +230,171 @@ impl Map {
 
         let mut hostiles = vec![];
 
-        // Do not spawn hostiles on starting tile
-        if pos.distance(Vector3::zero()) > 1.0 {
-            for _ in 0..(rng.gen::<f32>() * 4.0) as usize {
-                hostiles.push(self.add_hostile(rng, engine, pos, &decor_collisions));
+        if role == Role::Boss && !self.bosses.is_empty() {
+            let boss_type = &self.bosses[rng.gen_range(0..self.bosses.len())];
+            hostiles.push(self.add_boss(rng, engine, boss_type, pos, &decor_collisions));
+        } else {
+            for _ in 0..(rng.gen::<f32>() * role.hostile_budget()) as usize {
+                let hostile_type = &self.hostiles[rng.gen_range(0..self.hostiles.len())];
+                hostiles.push(self.add_hostile(rng, engine, hostile_type, pos, &decor_collisions));
             }
         }
 
         let collisions = self
             .tiles
             .collisions
-            .get(t)
-            .unwrap_or_else(|| panic!("Could not find collision for: {}!", name))
+            .get(&piece.model)
+            .unwrap_or_else(|| panic!("Could not find collision for: {}!", piece.model))
             .clone();
 
-        let model = engine.initialize_model(&self.tiles, t);
-        entity.insert(components::Tile::new(
-            model,
-            collisions,
-            pos,
-            self.tile_size,
-            -rot,
-            decor,
-            hostiles,
-        ));
+        let model = engine.initialize_model(&self.tiles, piece.model.as_str());
+        let mut tile = components::Tile::new(model, collisions, pos, self.tile_size, -rot, decor, hostiles);
+        tile.role = role;
+        tile.hazards = hazards;
+
+        // Doors are shared with the neighbour, only the +x and +z entrances are hung from this tile
+        tile.doors = [1, 2]
+            .into_iter()
+            .filter_map(|entrance| match doors[entrance] {
+                generator::Door::None => None,
+                generator::Door::Closed => Some(self.add_door(engine, pos, entrance, None, false)),
+                generator::Door::Lever => Some(self.add_door(engine, pos, entrance, None, true)),
+                generator::Door::Locked(lock) => Some(self.add_door(engine, pos, entrance, Some(lock), false)),
+            })
+            .collect();
+
+        tile.keys = keys
+            .into_iter()
+            .map(|lock| self.add_key(rng, engine, lock, pos, &decor_collisions))
+            .collect();
+
+        // Both sides of a lever door get their own lever
+        tile.objects = (0..4)
+            .filter(|entrance| doors[*entrance] == generator::Door::Lever)
+            .map(|entrance| self.add_lever(engine, pos, entrance))
+            .collect();
+
+        match role {
+            Role::Treasure => {
+                let chest = &self.theme.chest;
+                let position = self.find_free_position(rng, pos, &decor_collisions);
+                let action = components::InteractAction::Chest(chest.loot.clone());
+                let interactable = components::Interactable::new(CHEST_RADIUS, action, "Open chest");
+                tile.objects
+                    .push(self.add_object(engine, &chest.model, position, rng.gen::<f32>() * 360.0, interactable));
+            }
+            Role::Shrine => {
+                let shrine = &self.theme.shrine;
+                let position = self.find_free_position(rng, pos, &decor_collisions);
+                let action = components::InteractAction::Shrine(shrine.buff.clone());
+                let interactable = components::Interactable::new(SHRINE_RADIUS, action, "Pray");
+                tile.objects
+                    .push(self.add_object(engine, &shrine.model, position, 0.0, interactable));
+            }
+            _ => {}
+        }
+
+        entity.insert(tile);
+    }
+
+    fn add_door(
+        &self,
+        engine: &mut engine::Engine,
+        tile_center: Vector3<f32>,
+        entrance: usize,
+        lock: Option<usize>,
+        lever: bool,
+    ) -> components::TileDoor {
+        components::TileDoor {
+            model: engine.initialize_model(&self.decor, self.theme.door.as_str()),
+            collisions: self.decor.collisions.get(&self.theme.door).unwrap_or(&vec![]).clone(),
+            position: tile_center + entrance_direction(entrance) * self.tile_size / 2.0,
+            rotation: if entrance % 2 == 1 { 90.0 } else { 0.0 },
+            lock,
+            lever,
+        }
     }
 
-    fn add_decor(&self, engine: &mut engine::Engine, d: &decor::Decor, tile_center: Vector3<f32>, tile_rotation: f32) -> components::Decor {
+    /// Places the lever against the wall beside the door, inside this tile.
+    fn add_lever(&self, engine: &mut engine::Engine, tile_center: Vector3<f32>, entrance: usize) -> components::TileObject {
+        let direction = entrance_direction(entrance);
+        let door = tile_center + direction * self.tile_size / 2.0;
+        let position = door - direction * LEVER_WALL_DISTANCE + vec3(direction.z, 0.0, -direction.x) * LEVER_DOOR_DISTANCE;
+        let rotation = -(entrance as f32) * 90.0;
+
+        let interactable = components::Interactable::new(LEVER_RADIUS, components::InteractAction::Lever(door), "Pull lever");
+        self.add_object(engine, &self.theme.lever, position, rotation, interactable)
+    }
+
+    fn add_object(
+        &self,
+        engine: &mut engine::Engine,
+        model: &str,
+        position: Vector3<f32>,
+        rotation: f32,
+        interactable: components::Interactable,
+    ) -> components::TileObject {
+        components::TileObject {
+            model: engine.initialize_model(&self.decor, model),
+            collisions: self.decor.collisions.get(model).unwrap_or(&vec![]).clone(),
+            position,
+            rotation,
+            interactable,
+        }
+    }
+
+    fn add_key(
+        &self,
+        rng: &mut StdRng,
+        engine: &mut engine::Engine,
+        lock: usize,
+        tile_center: Vector3<f32>,
+        collisions: &[Polygon],
+    ) -> components::TileKey {
+        components::TileKey {
+            lock,
+            position: self.find_free_position(rng, tile_center, collisions),
+            model: self
+                .theme
+                .key_item
+                .as_ref()
+                .map(|key_item| engine.initialize_model(&self.decor, key_item.as_str())),
+        }
+    }
+
+    /// Random spot inside the tile, away from the walls, that does not collide with the decor.
+    fn find_free_position(&self, rng: &mut StdRng, tile_center: Vector3<f32>, collisions: &[Polygon]) -> Vector3<f32> {
+        let collider = vec![vec![vec2(-0.3, -0.3), vec2(0.3, -0.3), vec2(0.3, 0.3), vec2(-0.3, 0.3)]];
+        let mut position = tile_center;
+
+        for _ in 0..100 {
+            position = tile_center
+                + vec3(
+                    (rng.gen::<f32>() - 0.5) * (self.tile_size - 3.0),
+                    0.0,
+                    (rng.gen::<f32>() - 0.5) * (self.tile_size - 3.0),
+                );
+
+            if !is_colliding(&collider, position, collisions) {
+                break;
+            }
+        }
+
+        position
+    }
+
+    fn add_decor(
+        &self,
+        engine: &mut engine::Engine,
+        d: &decor::Placement,
+        tile_center: Vector3<f32>,
+        tile_rotation: f32,
+    ) -> components::Decor {
         let position = tile_center
             + Quaternion::from_angle_y(Deg(-tile_rotation)).rotate_vector(vec3(
-                d.pos[0] as f32 * config::GRID_DIST,
+                d.pos[0] * config::GRID_DIST,
                 0.0,
-                d.pos[1] as f32 * config::GRID_DIST,
+                d.pos[1] * config::GRID_DIST,
             ));
         let rotation = d.rotation - tile_rotation;
 
@@ -173,9 +415,39 @@ impl Map {
             })
             .collect();
 
-        let emitters = self
-            .decor
-            .get_emitters(&d.name)
+        let emitters = self.add_emitters(engine, &d.name, position, rotation);
+
+        let collisions = self.decor.collisions.get(&d.name).unwrap_or(&vec![]).clone();
+        let model = engine.initialize_model(&self.decor, d.name.as_str());
+
+        let destructible = self.theme.get_destructible(&d.name).cloned();
+        let broken_model = destructible
+            .as_ref()
+            .and_then(|destructible| destructible.broken.as_ref())
+            .map(|broken| engine.initialize_model(&self.decor, broken.as_str()));
+
+        components::Decor {
+            model,
+            collisions,
+            lights,
+            emitters,
+            position,
+            rotation,
+            destructible,
+            broken_model,
+            destroyed: false,
+        }
+    }
+
+    fn add_emitters(
+        &self,
+        engine: &mut engine::Engine,
+        name: &str,
+        position: Vector3<f32>,
+        rotation: f32,
+    ) -> Vec<components::DecorEmitter> {
+        self.decor
+            .get_emitters(name)
             .iter()
             .map(|e| {
                 let emitter_id = uuid::Uuid::new_v4().to_string();
@@ -191,22 +463,48 @@ impl Map {
                     size: e.size,
                     strength: e.strength,
                     flicker: e.flicker,
-                    position: position + Quaternion::from_angle_y(Deg(d.rotation - tile_rotation)).rotate_vector(e.position),
-                    rotation: d.rotation - tile_rotation,
+                    position: position + Quaternion::from_angle_y(Deg(rotation)).rotate_vector(e.position),
+                    rotation,
                 }
             })
-            .collect();
+            .collect()
+    }
 
-        let collisions = self.decor.collisions.get(&d.name).unwrap_or(&vec![]).clone();
-        let model = engine.initialize_model(&self.decor, d.name.as_str());
+    fn add_hazard(
+        &self,
+        rng: &mut StdRng,
+        engine: &mut engine::Engine,
+        h: HazardDefinition,
+        tile_center: Vector3<f32>,
+        tile_rotation: f32,
+    ) -> components::TileHazard {
+        let position = tile_center
+            + Quaternion::from_angle_y(Deg(-tile_rotation)).rotate_vector(vec3(
+                h.pos[0] as f32 * config::GRID_DIST,
+                0.0,
+                h.pos[1] as f32 * config::GRID_DIST,
+            ));
+        let rotation = -tile_rotation;
 
-        components::Decor {
-            model,
-            collisions,
-            lights,
-            emitters,
+        let mut emitters = match &h.emitter {
+            Some(emitter) => self.add_emitters(engine, emitter, position, rotation),
+            None => vec![],
+        };
+
+        if h.kind == HazardKind::Poison {
+            for emitter in emitters.iter_mut() {
+                emitter.start_color = vec3(0.3, 0.8, 0.1);
+                emitter.end_color = vec3(0.1, 0.3, 0.05);
+            }
+        }
+
+        components::TileHazard {
+            model: h.model.as_ref().map(|model| engine.initialize_model(&self.decor, model.as_str())),
+            offset: rng.gen::<f32>() * h.interval,
+            definition: h,
             position,
             rotation,
+            emitters,
         }
     }
 
@@ -214,21 +512,22 @@ impl Map {
         &self,
         rng: &mut StdRng,
         engine: &mut engine::Engine,
+        hostile_type: &HostileType,
         tile_center: Vector3<f32>,
         collisions: &[Polygon],
     ) -> components::Hostile {
-        let model = engine.initialize_model(&self.hostiles, "skeleton");
+        let definition = &hostile_type.definition;
+        let model = engine.initialize_model(&hostile_type.model, definition.model.as_str());
         let mut position;
 
-        let collider = self
-            .hostiles
+        let collider = hostile_type
+            .model
             .collisions
-            .get("skeleton")
-            .expect("Could not find skeleton collider!")
+            .get(&definition.model)
+            .unwrap_or_else(|| panic!("Could not find {} collider!", definition.model))
             .clone();
 
         loop {
-            let mut is_colliding = false;
             position = tile_center
                 + vec3(
                     (rng.gen::<f32>() - 0.5) * (self.tile_size - 3.0),
@@ -236,28 +535,90 @@ impl Map {
                     (rng.gen::<f32>() - 0.5) * (self.tile_size - 3.0),
                 );
 
-            for polygon in collider.iter() {
-                let p = polygon.transform(position, Quaternion::zero());
-                if engine::collision::check_collision_array(Vector3::zero(), &p, collisions) {
-                    is_colliding = true;
-                    break;
-                }
+            if !is_colliding(&collider, position, collisions) {
+                break;
             }
+        }
 
-            if !is_colliding {
-                break;
+        let mut route = vec![];
+        if rng.gen::<f32>() < definition.behaviour.patrol_chance {
+            let bounds = (self.tile_size - 3.0) / 2.0;
+            route.push(position);
+
+            for _ in 0..definition.behaviour.patrol_points {
+                for _ in 0..100 {
+                    let offset = vec3(rng.gen::<f32>() - 0.5, 0.0, rng.gen::<f32>() - 0.5) * definition.behaviour.patrol_radius * 2.0;
+                    let point = position + offset;
+
+                    if (point.x - tile_center.x).abs() < bounds
+                        && (point.z - tile_center.z).abs() < bounds
+                        && !is_colliding(&collider, point, collisions)
+                    {
+                        route.push(point);
+                        break;
+                    }
+                }
             }
         }
 
         components::Hostile {
+            definition: definition.clone(),
             model,
+            animation_graph: hostile_type.animation_graph.clone(),
             collider,
             position,
-            health: 10.0,
+            route,
+            boss: None,
         }
     }
 
-    fn add_grid(&self, world: &mut World, center: Vector3<f32>) {
+    fn add_boss(
+        &self,
+        rng: &mut StdRng,
+        engine: &mut engine::Engine,
+        boss_type: &HostileType,
+        tile_center: Vector3<f32>,
+        collisions: &[Polygon],
+    ) -> components::Hostile {
+        let mut boss = self.add_hostile(rng, engine, boss_type, tile_center, collisions);
+        let definition = boss_type
+            .definition
+            .boss
+            .as_ref()
+            .unwrap_or_else(|| panic!("{} is not a boss!", boss_type.key));
+
+        // Minions are placed up front so that summoning does not need to search for free space
+        let phases = definition
+            .phases
+            .iter()
+            .map(|phase| {
+                let minions = match &phase.summon {
+                    Some(summon) => {
+                        let minion_type = self
+                            .hostiles
+                            .iter()
+                            .find(|h| h.key == summon.hostile)
+                            .unwrap_or_else(|| panic!("Could not find minion: {}!", summon.hostile));
+
+                        (0..summon.count)
+                            .map(|_| self.add_hostile(rng, engine, minion_type, tile_center, collisions))
+                            .collect()
+                    }
+                    None => vec![],
+                };
+
+                components::BossPhase {
+                    definition: phase.clone(),
+                    minions,
+                }
+            })
+            .collect();
+
+        boss.boss = Some(components::Boss::new(phases, definition.reward.clone()));
+        boss
+    }
+
+    pub fn add_grid(&self, world: &mut World, center: Vector3<f32>) {
         for x in -config::GRID_COUNT..=config::GRID_COUNT {
             for z in -config::GRID_COUNT..=config::GRID_COUNT {
                 let off = vec3(x as f32 * config::GRID_DIST, 0.0, z as f32 * config::GRID_DIST);
@@ -293,28 +654,36 @@ pub fn edit_mode() -> Option<(String, usize)> {
     }
 }
 
-fn determine_tile(entrances: &[bool; 4]) -> (&str, f32) {
-    match entrances {
-        [true, false, false, false] => ("tile-catacombs-1000", 0.0),
-        [false, true, false, false] => ("tile-catacombs-1000", 90.0),
-        [false, false, true, false] => ("tile-catacombs-1000", 180.0),
-        [false, false, false, true] => ("tile-catacombs-1000", 270.0),
+/// Lays out a floor without touching the engine, so that tools running
+/// without a GPU can inspect the same layouts as the game.
+pub fn generate_layout(rng: &mut StdRng, floor: usize, grid_size: usize, number_of_tiles: usize) -> Vec<Vec<Option<generator::Tile>>> {
+    let layout = match layout_mode() {
+        Some(name) => generator::get_layout(&name),
+        None => generator::get_floor_layout(floor),
+    };
 
-        [true, true, false, false] => ("tile-catacombs-1100", 0.0),
-        [false, true, true, false] => ("tile-catacombs-1100", 90.0),
-        [false, false, true, true] => ("tile-catacombs-1100", 180.0),
-        [true, false, false, true] => ("tile-catacombs-1100", 270.0),
+    generator::generate(layout.as_ref(), rng, grid_size, number_of_tiles)
+}
 
-        [true, false, true, false] => ("tile-catacombs-1010", 0.0),
-        [false, true, false, true] => ("tile-catacombs-1010", 90.0),
+pub fn layout_mode() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
 
-        [true, true, true, false] => ("tile-catacombs-1110", 0.0),
-        [false, true, true, true] => ("tile-catacombs-1110", 90.0),
-        [true, false, true, true] => ("tile-catacombs-1110", 180.0),
-        [true, true, false, true] => ("tile-catacombs-1110", 270.0),
+    args.iter().position(|a| a == "--layout").and_then(|pos| args.get(pos + 1).cloned())
+}
 
-        [true, true, true, true] => ("tile-catacombs-1111", 0.0),
+/// Entrances are ordered -z, +x, +z, -x.
+fn entrance_direction(entrance: usize) -> Vector3<f32> {
+    [
+        vec3(0.0, 0.0, -1.0),
+        vec3(1.0, 0.0, 0.0),
+        vec3(0.0, 0.0, 1.0),
+        vec3(-1.0, 0.0, 0.0),
+    ][entrance]
+}
 
-        _ => ("tile-empty", 0.0),
-    }
+fn is_colliding(collider: &[Polygon], position: Vector3<f32>, collisions: &[Polygon]) -> bool {
+    collider.iter().any(|polygon| {
+        let p = polygon.transform(position, Quaternion::zero());
+        engine::collision::check_collision_array(Vector3::zero(), &p, collisions)
+    })
 }
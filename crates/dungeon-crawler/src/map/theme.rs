@@ -0,0 +1,117 @@
+use super::RewardDefinition;
+use engine::file;
+use serde_derive::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Piece {
+    pub entrances: [bool; 4],
+    pub model: String,
+    pub decor: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ChestDefinition {
+    pub model: String,
+    pub loot: Vec<LootDefinition>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BuffDefinition {
+    pub strength: u32,
+    pub dexterity: u32,
+    pub seconds: f32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ShrineDefinition {
+    pub model: String,
+    pub buff: BuffDefinition,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LootDefinition {
+    /// Every entry of a loot table is rolled on its own.
+    pub chance: f32,
+    pub reward: RewardDefinition,
+}
+
+/// Makes every decor with the given name breakable.
+#[derive(Clone, Debug, Deserialize)]
+pub struct DestructibleDefinition {
+    pub name: String,
+    pub vitality: u32,
+    /// Decor model swapped in once broken, without one the decor disappears.
+    pub broken: Option<String>,
+    pub loot: Vec<LootDefinition>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Theme {
+    #[serde(skip)]
+    pub key: String,
+    pub name: String,
+    pub tiles_model: String,
+    pub decor_model: String,
+    pub empty_tile: String,
+    pub ambience: String,
+    /// Decor model hung in the entrances that have a door.
+    pub door: String,
+    /// Decor model of the keys, keys without one are only a glowing light.
+    pub key_item: Option<String>,
+    /// Decor model of the levers next to the doors operated by them.
+    pub lever: String,
+    /// Opened for its loot, one is placed in every treasure room.
+    pub chest: ChestDefinition,
+    /// Grants a buff once, one is placed in every shrine room.
+    pub shrine: ShrineDefinition,
+    #[serde(default)]
+    pub destructibles: Vec<DestructibleDefinition>,
+    pub pieces: Vec<Piece>,
+    pub hostiles: Vec<String>,
+    pub bosses: Vec<String>,
+}
+
+impl Theme {
+    /// Finds the piece whose entrances, rotated clockwise in steps of 90 degrees, match the given ones.
+    pub fn determine_tile(&self, entrances: &[bool; 4]) -> Option<(&Piece, f32)> {
+        self.pieces.iter().find_map(|piece| {
+            (0..4)
+                .find(|rotation| (0..4).all(|i| entrances[i] == piece.entrances[(i + 4 - rotation) % 4]))
+                .map(|rotation| (piece, rotation as f32 * 90.0))
+        })
+    }
+
+    pub fn get_piece(&self, decor: &str) -> Option<&Piece> {
+        self.pieces.iter().find(|p| p.decor == decor)
+    }
+
+    pub fn get_destructible(&self, decor: &str) -> Option<&DestructibleDefinition> {
+        self.destructibles.iter().find(|d| d.name == decor)
+    }
+
+    pub fn decor_path(&self, piece: &Piece) -> String {
+        format!("{}/{}", self.key, piece.decor)
+    }
+}
+
+#[derive(Deserialize)]
+struct Themes {
+    floors: Vec<String>,
+}
+
+/// Key of the theme used on the given floor, past the end of the list the floors start over from the first theme.
+pub fn get_floor_theme(floor: usize) -> String {
+    match serde_json::from_str::<Themes>(file::read_string("tiles/themes.json").as_str()) {
+        Ok(themes) => themes.floors[floor.saturating_sub(1) % themes.floors.len()].clone(),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+pub fn get_theme(key: &str) -> Theme {
+    let path = format!("tiles/{}/theme.json", key);
+
+    match serde_json::from_str::<Theme>(file::read_string(&path).as_str()) {
+        Ok(theme) => Theme { key: key.into(), ..theme },
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -72,6 +72,12 @@ impl State {
         if r == PressState::Pressed(false) {
             self.world.init(&mut self.engine);
         }
+
+        if let Some(key) = keyboard_input.virtual_keycode {
+            if keyboard_input.state == winit::event::ElementState::Pressed {
+                self.world.editor_keyboard(key);
+            }
+        }
     }
 
     pub fn mouse_move(&mut self, id: u64, x: f32, y: f32) {
@@ -97,12 +103,17 @@ impl State {
         self.engine.shadow_pipeline.update(&self.engine.ctx, &self.world.components);
         self.views.update(&mut self.engine, &mut self.world, last_frame);
 
+        let editing = self.world.editor.is_some() && self.world.game_state == GameState::Running;
+        if editing {
+            self.world.update_editor(&mut self.engine, self.views.is_click_through(&0));
+        }
+
         let mut input = self.world.components.get_resource_mut::<Input>().unwrap();
         let pressed_buttons = input.pressed_buttons();
         let views = &self.views;
 
         let joystick = {
-            if input.joystick.is_none() {
+            if input.joystick.is_none() && !editing {
                 let first = pressed_buttons.iter().find(|(id, _)| !views.is_click_through(id));
 
                 if let Some((id, _)) = first {
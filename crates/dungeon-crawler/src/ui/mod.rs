@@ -30,6 +30,7 @@ impl Views {
         ImageContext::add_texture(engine, "health", engine::file::read_bytes("icons/health.png"));
         ImageContext::add_texture(engine, "attack", engine::file::read_bytes("icons/attack.png"));
         ImageContext::add_texture(engine, "check", engine::file::read_bytes("icons/check.png"));
+        ImageContext::add_texture(engine, "dodge", engine::file::read_bytes("icons/dodge.png"));
 
         Self {
             ui_scale: 2000.0 / scale / engine.ctx.settings.ui_scale,
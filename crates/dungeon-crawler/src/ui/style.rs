@@ -22,6 +22,7 @@ pub const PALETTE_LIGHT_GRAY: Vector3<f32> = Vector3::new(0.399, 0.399, 0.399);
 pub const PALETTE_GRAY: Vector3<f32> = Vector3::new(0.133, 0.133, 0.133);
 // pub const PALETTE_RED: Vector3<f32> = Vector3::new(0.435, 0.043, 0.0);
 pub const PALETTE_LIGHT_RED: Vector3<f32> = Vector3::new(0.870, 0.086, 0.0);
+pub const PALETTE_LIGHT_GREEN: Vector3<f32> = Vector3::new(0.357, 0.667, 0.196);
 pub const TEXT: Vector3<f32> = Vector3::new(1.0, 1.0, 1.0);
 
 pub const SHADOW_S: f32 = 2.0;
@@ -1,4 +1,5 @@
 use crate::{
+    map,
     ui::style,
     world::{
         self,
@@ -16,6 +17,7 @@ fn status_bar(label: &str, value: f32, max_value: f32, color: Vector3<f32>) -> B
         label,
         BarProps {
             width: Dimension::Percent(1.0),
+            height: Dimension::Auto,
             value,
             max_value,
             border_color: style::PALETTE_LIGHT_GOLD.extend(1.0),
@@ -29,6 +31,33 @@ fn status_bar(label: &str, value: f32, max_value: f32, color: Vector3<f32>) -> B
     )
 }
 
+fn boss_bar(world: &mut world::World) -> Option<Box<DisplayWidget>> {
+    let mut query = world.components.query::<(
+        &components::Name,
+        &components::Stats,
+        &components::Agressor,
+        With<components::Boss>,
+    )>();
+    let (name, stats, _, _) = query.iter(&world.components).find(|(_, _, agressor, _)| agressor.is_aggressive)?;
+
+    Some(Bar::default().draw(
+        &name.name,
+        BarProps {
+            width: Dimension::Percent(1.0),
+            height: Dimension::Points(style::SL),
+            value: stats.health.get(),
+            max_value: stats.get_base_health(),
+            border_color: style::PALETTE_LIGHT_GOLD.extend(1.0),
+            margin: Rect::<Dimension>::from_points(0.0, 0.0, 0.0, style::SS),
+            color: (style::PALETTE_LIGHT_RED * 0.4).extend(1.0),
+            gradient: Some(Gradient {
+                background_end: style::PALETTE_LIGHT_RED.extend(1.0),
+                angle: 180.0,
+            }),
+        },
+    ))
+}
+
 fn action_button(button: &Button, icon: &str, foreground: Vector3<f32>, icon_size: f32, padding: f32) -> Box<DisplayWidget> {
     button.draw(ButtonProps {
         icon: Some((icon.into(), icon_size)),
@@ -41,6 +70,79 @@ fn action_button(button: &Button, icon: &str, foreground: Vector3<f32>, icon_siz
     })
 }
 
+fn editor_text(text: String) -> Box<TextWidget> {
+    TextWidget::new(
+        TextData { text, size: style::BODY2 },
+        Rect::<Dimension>::from_points(0.0, 0.0, 0.0, style::SS),
+        AlignSelf::FlexStart,
+    )
+}
+
+fn editor_button(button: &Button, text: &str) -> Box<DisplayWidget> {
+    button.draw(ButtonProps {
+        text: Some((text.into(), style::BODY2)),
+        background: vec4(0.0, 0.0, 0.0, 0.7),
+        margin: Rect::<Dimension>::from_points(0.0, style::SS, 0.0, style::SS),
+        padding: Rect::<Dimension>::from_points(style::SS, style::SS, style::SS, style::SS),
+        border_radius: Dimension::Points(style::RADIUS_M),
+        ..Default::default()
+    })
+}
+
+fn editor_panel(ui_state: &mut ui::State, editor: &mut map::Editor) -> Box<NodeWidget> {
+    let buttons: Vec<(Button, &str, fn(&mut map::Editor))> = vec![
+        (Button::new("editor_previous_decor"), "< Decor", |e| e.cycle_brush(-1)),
+        (Button::new("editor_next_decor"), "Decor >", |e| e.cycle_brush(1)),
+        (Button::new("editor_rotate"), "Rotate", |e| e.rotate()),
+        (Button::new("editor_delete"), "Delete", |e| e.delete()),
+        (Button::new("editor_previous_variant"), "< Variant", |e| e.cycle_variant(-1)),
+        (Button::new("editor_next_variant"), "Variant >", |e| e.cycle_variant(1)),
+        (Button::new("editor_add_variant"), "New variant", |e| e.add_variant()),
+        (Button::new("editor_duplicate_variant"), "Duplicate", |e| e.duplicate_variant()),
+        (Button::new("editor_save"), "Save", |e| e.save()),
+    ];
+
+    for (button, _, action) in buttons.iter() {
+        if ui_state.clicked(&button.key, true).is_some() {
+            action(editor);
+        }
+    }
+
+    let selected = match editor.selected.and_then(|index| editor.decor().get(index)) {
+        Some(decor) => format!("Selected: {} at {},{}", decor.name, decor.pos[0], decor.pos[1]),
+        None => "Selected: none".into(),
+    };
+
+    let mut children: Vec<Box<dyn BaseWidget>> = vec![
+        editor_text(format!(
+            "Tile: {}{}",
+            editor.path,
+            if editor.unsaved { " (unsaved)" } else { "" }
+        )),
+        editor_text(format!("Variant: {} / {}", editor.variant + 1, editor.variants.len())),
+        editor_text(format!("Decor: {} ({} deg)", editor.brush_name(), editor.rotation)),
+        editor_text(selected),
+    ];
+
+    if !editor.status.is_empty() {
+        children.push(editor_text(editor.status.clone()));
+    }
+
+    children.extend(
+        buttons
+            .iter()
+            .map(|(button, text, _)| editor_button(button, text) as Box<dyn BaseWidget>),
+    );
+
+    NodeWidget::new(Style {
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::FlexStart,
+        padding: Rect::<Dimension>::from_points(0.0, 0.0, style::SM, 0.0),
+        ..Default::default()
+    })
+    .with_children(children)
+}
+
 fn top_bar(ctx: &mut engine::Context, world: &mut world::World) -> Box<NodeWidget> {
     let mut top_left: Vec<Box<dyn BaseWidget>> = vec![];
 
@@ -69,6 +171,31 @@ fn top_bar(ctx: &mut engine::Context, world: &mut world::World) -> Box<NodeWidge
         style::PALETTE_LIGHT_GOLD,
     ));
 
+    let max_stamina = stats.get_base_stamina();
+    top_left.push(status_bar(
+        &format!("{} / {}", stats.stamina.floor(), max_stamina),
+        stats.stamina,
+        max_stamina,
+        style::PALETTE_LIGHT_GREEN,
+    ));
+
+    let keys = world
+        .components
+        .query_filtered::<&components::Inventory, With<components::UserControl>>()
+        .get_single(&world.components)
+        .map_or(0, |inventory| inventory.keys.len());
+
+    if keys > 0 {
+        top_left.push(NodeWidget::new(Style::default()).with_children(vec![TextWidget::new(
+            TextData {
+                text: format!("Keys: {}", keys),
+                size: style::BODY2,
+            },
+            Default::default(),
+            AlignSelf::FlexStart,
+        )]));
+    }
+
     if ctx.settings.show_fps {
         let fps = world.components.get_resource::<resources::Fps>().unwrap();
         top_left.push(NodeWidget::new(Style::default()).with_children(vec![TextWidget::new(
@@ -81,6 +208,11 @@ fn top_bar(ctx: &mut engine::Context, world: &mut world::World) -> Box<NodeWidge
         )]));
     }
 
+    let mut top_center: Vec<Box<dyn BaseWidget>> = vec![];
+    if let Some(boss_bar) = boss_bar(world) {
+        top_center.push(boss_bar);
+    }
+
     let mut top_right: Vec<Box<dyn BaseWidget>> = vec![];
     let display_target: Vec<&components::DisplayTarget> = world
         .components
@@ -119,6 +251,16 @@ fn top_bar(ctx: &mut engine::Context, world: &mut world::World) -> Box<NodeWidge
             ..Default::default()
         })
         .with_children(top_left),
+        NodeWidget::new(Style {
+            flex_direction: FlexDirection::Column,
+            size: Size {
+                width: Dimension::Percent(0.4),
+                height: Dimension::Auto,
+            },
+            padding: Rect::<Dimension>::from_points(style::SM, style::SM, 0.0, 0.0),
+            ..Default::default()
+        })
+        .with_children(top_center),
         NodeWidget::new(Style {
             flex_direction: FlexDirection::Column,
             size: Size {
@@ -143,10 +285,54 @@ pub fn game(ctx: &mut engine::Context, ui_state: &mut ui::State, world: &mut wor
 
     let attack_button = Button::new("attack_button");
     let health_button = Button::new("health_button");
+    let interact_button = Button::new("interact_button");
+    let dodge_button = Button::new("dodge_button");
 
     for mut user_control in world.components.query::<&mut UserControl>().iter_mut(&mut world.components) {
         user_control.set_from_ui(UiActionCode::Attack, ui_state.mouse_down(&attack_button.key).is_some());
         user_control.set_from_ui(UiActionCode::Health, ui_state.mouse_down(&health_button.key).is_some());
+        user_control.set_from_ui(UiActionCode::Interact, ui_state.mouse_down(&interact_button.key).is_some());
+        user_control.set_from_ui(UiActionCode::Dodge, ui_state.mouse_down(&dodge_button.key).is_some());
+    }
+
+    let interaction = world
+        .components
+        .query::<&components::DisplayInteraction>()
+        .iter(&world.components)
+        .next()
+        .map(|interaction| interaction.prompt.clone());
+
+    let mut actions: Vec<Box<dyn BaseWidget>> = vec![action_button(&menu_button, "menu", style::TEXT, style::ICON_M, style::SM)];
+    if let Some(prompt) = interaction {
+        actions.push(TextWidget::new(
+            TextData {
+                text: prompt,
+                size: style::BODY2,
+            },
+            Rect::<Dimension>::from_points(style::SM, 0.0, 0.0, style::SS),
+            AlignSelf::Center,
+        ));
+        actions.push(action_button(&interact_button, "check", style::TEXT, style::ICON_M, style::SM));
+    }
+    actions.push(action_button(&dodge_button, "dodge", style::TEXT, style::ICON_M, style::SM));
+    actions.push(action_button(
+        &attack_button,
+        "attack",
+        style::PALETTE_LIGHT_GOLD,
+        style::ICON_L,
+        style::SL,
+    ));
+    actions.push(action_button(
+        &health_button,
+        "health",
+        style::PALETTE_LIGHT_RED,
+        style::ICON_L,
+        style::SL,
+    ));
+
+    let mut top: Vec<Box<dyn BaseWidget>> = vec![top_bar(ctx, world)];
+    if let Some(editor) = &mut world.editor {
+        top.push(editor_panel(ui_state, editor));
     }
 
     NodeWidget::new(Style {
@@ -160,7 +346,11 @@ pub fn game(ctx: &mut engine::Context, ui_state: &mut ui::State, world: &mut wor
         ..Default::default()
     })
     .with_children(vec![
-        top_bar(ctx, world),
+        NodeWidget::new(Style {
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(top),
         NodeWidget::new(Style {
             justify_content: JustifyContent::FlexEnd,
             align_items: AlignItems::FlexEnd,
@@ -170,10 +360,6 @@ pub fn game(ctx: &mut engine::Context, ui_state: &mut ui::State, world: &mut wor
             },
             ..Default::default()
         })
-        .with_children(vec![
-            action_button(&menu_button, "menu", style::TEXT, style::ICON_M, style::SM),
-            action_button(&attack_button, "attack", style::PALETTE_LIGHT_GOLD, style::ICON_L, style::SL),
-            action_button(&health_button, "health", style::PALETTE_LIGHT_RED, style::ICON_L, style::SL),
-        ]),
+        .with_children(actions),
     ])
 }
@@ -1,6 +1,6 @@
 use crate::config;
 use bevy_ecs::prelude::*;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Action {
@@ -8,6 +8,21 @@ pub enum Action {
     Attack,
     Hit,
     Death,
+    /// Roll in the movement direction, attacks miss during the first part of it.
+    Dodge,
+}
+
+impl Action {
+    /// Name the animation graphs match against.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::None => "none",
+            Action::Attack => "attack",
+            Action::Hit => "hit",
+            Action::Death => "death",
+            Action::Dodge => "dodge",
+        }
+    }
 }
 
 #[derive(Component)]
@@ -50,8 +65,37 @@ impl ActionExecutor {
         self.executed = false;
     }
 
+    /// How far the current action is, from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        if self.length > 0.0 {
+            (self.set.elapsed().as_secs_f32() / self.length).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Seconds since `action` ended, when it was the last one set.
+    pub fn since_finished(&self, action: Action) -> Option<f32> {
+        if self.current != action {
+            return None;
+        }
+
+        let since = self.set.elapsed().as_secs_f32() - self.length;
+        (since >= 0.0).then_some(since)
+    }
+
+    /// Pauses the current action, its timings move back by `duration`.
+    pub fn delay(&mut self, duration: Duration) {
+        self.set += duration;
+    }
+
     pub fn should_execute(&mut self) -> bool {
         let activated = self.set.elapsed().as_secs_f32() >= self.activation_time;
+        self.execute_once(activated)
+    }
+
+    /// Like `should_execute`, but activated by something else, such as an animation event.
+    pub fn execute_once(&mut self, activated: bool) -> bool {
         if activated && !self.executed {
             self.executed = true;
             return true;
@@ -63,7 +107,7 @@ impl ActionExecutor {
 
 fn is_forced(action: Action) -> bool {
     match action {
-        Action::None | Action::Attack => false,
+        Action::None | Action::Attack | Action::Dodge => false,
         Action::Hit | Action::Death => true,
     }
 }
@@ -1,20 +1,61 @@
+use crate::map::BehaviourDefinition;
 use bevy_ecs::prelude::*;
+use cgmath::*;
+use std::time::Instant;
 
 #[derive(Component, Debug)]
 pub struct Agressor {
     pub start_range: f32,
     pub end_range: f32,
+    pub hearing_range: f32,
+    pub memory_time: f32,
+    pub alert_radius: f32,
+    pub alarm_sound: Option<String>,
     pub is_aggressive: bool,
+    pub visible_target: Option<Vector3<f32>>,
+    pub target_radius: f32,
+    pub last_known_position: Option<Vector3<f32>>,
+    pub last_seen: Instant,
 }
 
 impl Agressor {
-    pub fn new(range: f32) -> Self {
+    pub fn new(definition: &BehaviourDefinition) -> Self {
         Self {
-            start_range: range,
-            end_range: range * 1.5,
+            start_range: definition.sight_range,
+            end_range: definition.leash_range,
+            hearing_range: definition.hearing_range,
+            memory_time: definition.memory_time,
+            alert_radius: definition.alert_radius,
+            alarm_sound: definition.alarm_sound.clone(),
             is_aggressive: false,
+            visible_target: None,
+            target_radius: 0.0,
+            last_known_position: None,
+            last_seen: Instant::now(),
         }
     }
+
+    pub fn see(&mut self, position: Vector3<f32>, radius: f32) {
+        self.visible_target = Some(position);
+        self.target_radius = radius;
+        self.alert(position);
+    }
+
+    pub fn alert(&mut self, position: Vector3<f32>) {
+        self.is_aggressive = true;
+        self.last_known_position = Some(position);
+        self.last_seen = Instant::now();
+    }
+
+    pub fn calm(&mut self) {
+        self.is_aggressive = false;
+        self.visible_target = None;
+        self.last_known_position = None;
+    }
+
+    pub fn has_forgotten(&self) -> bool {
+        self.last_seen.elapsed().as_secs_f32() > self.memory_time
+    }
 }
 
 #[derive(Component)]
@@ -1,9 +1,22 @@
+use crate::map::DamageType;
 use bevy_ecs::prelude::*;
+use cgmath::*;
+use engine::collision::HitShape;
 use std::ops::Range;
 
 #[derive(Component)]
 pub struct Attack {
+    pub attacker: Entity,
+    /// Where the attacker stood, targets are knocked away from it.
+    pub origin: Vector3<f32>,
+    pub direction: Vector2<f32>,
     pub team: usize,
     pub damage: Range<f32>,
-    pub radius: f32,
+    pub damage_type: DamageType,
+    pub critical_chance: f32,
+    pub shape: HitShape,
+    pub max_targets: Option<usize>,
+    pub line_of_sight: bool,
+    pub knockback: f32,
+    pub hit_stop: u32,
 }
@@ -0,0 +1,83 @@
+use crate::map::BehaviourDefinition;
+use bevy_ecs::prelude::*;
+use cgmath::*;
+use rand::Rng;
+use std::{ops::RangeInclusive, time::Instant};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BehaviourState {
+    Idle,
+    Patrol,
+    Chase,
+    Attack,
+    Return,
+}
+
+#[derive(Component, Debug)]
+pub struct Behaviour {
+    pub state: BehaviourState,
+    pub changed: Instant,
+    pub spawn: Vector3<f32>,
+    pub destination: Vector3<f32>,
+    pub route: Vec<Vector3<f32>>,
+    pub route_index: usize,
+    pub slot: Option<Vector3<f32>>,
+    pub idle_time: RangeInclusive<f32>,
+    pub wait_time: f32,
+    pub wander_radius: f32,
+    pub walk_speed: f32,
+    pub chase_speed: f32,
+    pub regeneration: f32,
+}
+
+impl Behaviour {
+    pub fn new(spawn: Vector3<f32>, route: Vec<Vector3<f32>>, definition: &BehaviourDefinition) -> Self {
+        let [a, b] = definition.idle_time;
+        let (idle_min, idle_max) = (a.min(b), a.max(b));
+
+        Self {
+            state: BehaviourState::Idle,
+            changed: Instant::now(),
+            spawn,
+            destination: spawn,
+            route,
+            route_index: 0,
+            slot: None,
+            // Inclusive and ordered, so a fixed idle time like [2.0, 2.0] is fine too
+            idle_time: idle_min..=idle_max,
+            wait_time: idle_min,
+            wander_radius: definition.wander_radius,
+            walk_speed: definition.walk_speed,
+            chase_speed: definition.chase_speed,
+            regeneration: definition.regeneration,
+        }
+    }
+
+    pub fn set_state(&mut self, state: BehaviourState) {
+        if self.state != state {
+            self.state = state;
+            self.changed = Instant::now();
+        }
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.changed.elapsed().as_secs_f32()
+    }
+
+    pub fn idle<R: Rng>(&mut self, rng: &mut R) {
+        self.wait_time = rng.gen_range(self.idle_time.clone());
+        self.set_state(BehaviourState::Idle);
+    }
+
+    pub fn patrol<R: Rng>(&mut self, rng: &mut R) {
+        self.destination = if self.route.is_empty() {
+            let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+            self.spawn + vec3(angle.sin(), 0.0, angle.cos()) * rng.gen::<f32>() * self.wander_radius
+        } else {
+            self.route_index = (self.route_index + 1) % self.route.len();
+            self.route[self.route_index]
+        };
+
+        self.set_state(BehaviourState::Patrol);
+    }
+}
@@ -0,0 +1,33 @@
+use super::Hostile;
+use crate::map::{PhaseDefinition, RewardDefinition};
+use bevy_ecs::prelude::*;
+
+#[derive(Clone)]
+pub struct BossPhase {
+    pub definition: PhaseDefinition,
+    pub minions: Vec<Hostile>,
+}
+
+#[derive(Component, Clone)]
+pub struct Boss {
+    pub phases: Vec<BossPhase>,
+    pub phase: usize,
+    pub reward: RewardDefinition,
+}
+
+impl Boss {
+    pub fn new(phases: Vec<BossPhase>, reward: RewardDefinition) -> Self {
+        Self { phases, phase: 0, reward }
+    }
+
+    /// Returns the next phase once health drops to its threshold.
+    pub fn next_phase(&mut self, health: f32) -> Option<&BossPhase> {
+        match self.phases.get(self.phase) {
+            Some(phase) if health <= phase.definition.health => {
+                self.phase += 1;
+                self.phases.get(self.phase - 1)
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,17 +1,22 @@
 use bevy_ecs::prelude::*;
-use engine::collision::Polygon;
+use cgmath::*;
+use engine::collision::{Polygon, PolygonMethods};
 
 #[derive(Component, Clone, Debug)]
 pub struct Collision {
     pub key: String,
     pub polygons: Vec<Polygon>,
+    pub radius: f32,
 }
 
 impl Collision {
     pub fn new(polygons: Vec<Polygon>) -> Self {
+        let radius = polygons.iter().map(|p| p.radius(Vector2::zero())).fold(0.0, f32::max);
+
         Self {
             key: uuid::Uuid::new_v4().to_string(),
             polygons,
+            radius,
         }
     }
 }
@@ -0,0 +1,18 @@
+use crate::map::{LootDefinition, RewardDefinition};
+use bevy_ecs::prelude::*;
+
+/// Breakable decor, `decor` is its index in the decor of `tile` so the
+/// broken state survives the tile being spawned again.
+#[derive(Component)]
+pub struct Destructible {
+    pub tile: Entity,
+    pub decor: usize,
+    pub broken: Option<engine::ecs::components::Model>,
+    pub loot: Vec<LootDefinition>,
+}
+
+/// Loot lying on the floor, picked up by walking over it.
+#[derive(Component)]
+pub struct Loot {
+    pub reward: RewardDefinition,
+}
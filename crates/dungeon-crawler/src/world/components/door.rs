@@ -0,0 +1,34 @@
+use bevy_ecs::prelude::*;
+use cgmath::*;
+use engine::collision::Polygon;
+
+/// Progress runs from 0.0 (closed) to 1.0 (open) in both directions.
+pub enum DoorState {
+    Closed,
+    Opening(f32),
+    Open,
+    Closing(f32),
+}
+
+#[derive(Component)]
+pub struct Door {
+    pub lock: Option<usize>,
+    pub state: DoorState,
+    pub closed_position: Vector3<f32>,
+    pub collisions: Vec<Polygon>,
+}
+
+impl Door {
+    pub fn new(lock: Option<usize>, closed_position: Vector3<f32>, collisions: Vec<Polygon>) -> Self {
+        Self {
+            lock,
+            state: DoorState::Closed,
+            closed_position,
+            collisions,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self.state, DoorState::Closed)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    config,
+    map::{DamageType, HazardDefinition, HazardKind},
+};
+use bevy_ecs::prelude::*;
+use cgmath::*;
+
+#[derive(PartialEq)]
+pub enum HazardPhase {
+    Idle,
+    /// Telegraph with its progress from 0.0 to 1.0.
+    Warning(f32),
+    Active,
+}
+
+#[derive(Component)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    pub radius: f32,
+    pub damage: f32,
+    pub interval: f32,
+    pub active: f32,
+    pub warning: f32,
+    pub sound: Option<String>,
+    pub timer: f32,
+    pub disarmed: bool,
+    /// Entities already hit by the current trigger, or the current burn tick.
+    pub hit: Vec<Entity>,
+}
+
+impl Hazard {
+    pub fn new(definition: &HazardDefinition, offset: f32) -> Self {
+        Self {
+            kind: definition.kind,
+            radius: definition.radius * config::GRID_DIST,
+            damage: definition.damage,
+            interval: definition.interval,
+            active: definition.active,
+            warning: definition.warning,
+            sound: definition.sound.clone(),
+            timer: offset,
+            disarmed: false,
+            hit: vec![],
+        }
+    }
+
+    pub fn color(&self) -> Vector3<f32> {
+        match self.kind {
+            HazardKind::Spikes => vec3(1.0, 0.2, 0.1),
+            HazardKind::Fire => vec3(1.0, 0.5, 0.1),
+            HazardKind::Poison => vec3(0.3, 1.0, 0.2),
+        }
+    }
+
+    pub fn damage_type(&self) -> DamageType {
+        match self.kind {
+            HazardKind::Spikes => DamageType::Physical,
+            HazardKind::Fire => DamageType::Fire,
+            HazardKind::Poison => DamageType::Poison,
+        }
+    }
+
+    pub fn phase(&self) -> HazardPhase {
+        let time = self.timer % self.interval;
+        let active_start = self.interval - self.active;
+        let warning_start = active_start - self.warning;
+
+        if self.disarmed || time < warning_start {
+            HazardPhase::Idle
+        } else if time < active_start {
+            HazardPhase::Warning((time - warning_start) / self.warning)
+        } else {
+            HazardPhase::Active
+        }
+    }
+}
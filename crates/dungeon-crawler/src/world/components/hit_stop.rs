@@ -0,0 +1,13 @@
+use bevy_ecs::prelude::*;
+
+/// Freezes animation and the current action for a few fixed steps after a heavy hit.
+#[derive(Component)]
+pub struct HitStop {
+    pub steps: u32,
+}
+
+impl HitStop {
+    pub fn new(steps: u32) -> Self {
+        Self { steps }
+    }
+}
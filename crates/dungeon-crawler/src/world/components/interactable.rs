@@ -0,0 +1,48 @@
+use crate::map::{BuffDefinition, LootDefinition};
+use bevy_ecs::prelude::*;
+use cgmath::*;
+
+#[derive(Clone)]
+pub enum InteractAction {
+    /// Opens the door on the same entity, locked doors need their key.
+    Door,
+    /// Toggles the door whose closed position is the given one.
+    Lever(Vector3<f32>),
+    /// Drops the pickups rolled from the loot table.
+    Chest(Vec<LootDefinition>),
+    Shrine(BuffDefinition),
+    /// Stops the hazard on the same entity for good.
+    Disarm,
+}
+
+/// Something the player can use when standing in front of it.
+#[derive(Component, Clone)]
+pub struct Interactable {
+    pub radius: f32,
+    pub action: InteractAction,
+    pub prompt: String,
+}
+
+impl Interactable {
+    pub fn new(radius: f32, action: InteractAction, prompt: &str) -> Self {
+        Self {
+            radius,
+            action,
+            prompt: prompt.into(),
+        }
+    }
+}
+
+/// The interactable the player would use, shown as a prompt next to the interact button.
+#[derive(Component, Clone)]
+pub struct DisplayInteraction {
+    pub prompt: String,
+}
+
+/// A timed bonus on top of the stats, taken off again when it runs out.
+#[derive(Component)]
+pub struct Buff {
+    pub strength: u32,
+    pub dexterity: u32,
+    pub remaining: f32,
+}
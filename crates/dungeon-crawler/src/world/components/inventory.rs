@@ -0,0 +1,12 @@
+use bevy_ecs::prelude::*;
+
+#[derive(Component, Default)]
+pub struct Inventory {
+    pub keys: Vec<usize>,
+}
+
+/// A key lying on the floor, opens the doors locked with the same number.
+#[derive(Component)]
+pub struct Key {
+    pub lock: usize,
+}
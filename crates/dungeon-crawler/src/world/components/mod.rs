@@ -1,9 +1,17 @@
 mod action;
 mod aggression;
 mod attack;
+mod behaviour;
+mod boss;
 mod collision;
+mod destructible;
 mod display_target;
+mod door;
 mod flicker;
+mod hazard;
+mod hit_stop;
+mod interactable;
+mod inventory;
 mod movement;
 mod name;
 pub mod stats;
@@ -16,10 +24,18 @@ pub use action::Action;
 pub use action::ActionExecutor;
 pub use aggression::*;
 pub use attack::Attack;
+pub use behaviour::*;
+pub use boss::*;
 pub use collision::Collision;
+pub use destructible::*;
 pub use display_target::DisplayTarget;
+pub use door::*;
 pub use flicker::Flicker;
-pub use movement::Movement;
+pub use hazard::*;
+pub use hit_stop::HitStop;
+pub use interactable::*;
+pub use inventory::*;
+pub use movement::*;
 pub use name::Name;
 pub use stats::*;
 pub use target::Target;
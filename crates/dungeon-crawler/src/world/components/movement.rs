@@ -1,6 +1,11 @@
 use bevy_ecs::prelude::*;
 use cgmath::*;
 
+/// Above this velocity the character runs, which costs the player stamina.
+pub const RUN_VELOCITY: f32 = 0.08;
+/// How much of the knockback is left for the next step.
+pub const KNOCKBACK_DAMPING: f32 = 0.8;
+
 #[derive(Component)]
 pub struct Movement {
     pub max_velocity: f32,
@@ -8,6 +13,8 @@ pub struct Movement {
     pub target_velocity: f32,
     pub direction: f32,
     pub to: Vector3<f32>,
+    /// Push from a hit, added to every step while it fades out.
+    pub knockback: Vector3<f32>,
 }
 
 impl Movement {
@@ -18,6 +25,7 @@ impl Movement {
             target_velocity: 0.0,
             direction: 0.0,
             to: Vector3::zero(),
+            knockback: Vector3::zero(),
         }
     }
 
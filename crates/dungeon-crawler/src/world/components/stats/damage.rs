@@ -0,0 +1,130 @@
+use crate::map::{DamageType, Resistances};
+
+/// Armor that halves physical damage, every point after that counts a little less.
+const ARMOR_HALVING: f32 = 10.0;
+/// Resistances above this are capped so nothing becomes immune.
+const MAX_RESISTANCE: f32 = 0.75;
+pub const CRITICAL_MULTIPLIER: f32 = 1.5;
+/// However well protected the target is, a hit always takes off at least this much.
+pub const MINIMUM_DAMAGE: f32 = 1.0;
+
+/// Health taken off by a hit of `amount` raw damage, `critical` has already been rolled by the attacker.
+/// Armor only mitigates physical damage, resistances apply to their own type, the result is rounded.
+pub fn resolve_damage(amount: f32, damage_type: DamageType, critical: bool, armor: f32, resistances: &Resistances) -> f32 {
+    if amount <= 0.0 {
+        return 0.0;
+    }
+
+    let mut damage = amount;
+    if critical {
+        damage *= CRITICAL_MULTIPLIER;
+    }
+
+    if damage_type == DamageType::Physical {
+        let armor = armor.max(0.0);
+        damage *= ARMOR_HALVING / (ARMOR_HALVING + armor);
+    }
+
+    damage *= 1.0 - resistances.get(damage_type).min(MAX_RESISTANCE);
+
+    damage.round().max(MINIMUM_DAMAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resistances(physical: f32, fire: f32, poison: f32) -> Resistances {
+        Resistances { physical, fire, poison }
+    }
+
+    #[test]
+    fn armor_mitigates_physical_damage() {
+        let none = Resistances::default();
+
+        assert_eq!(resolve_damage(20.0, DamageType::Physical, false, 0.0, &none), 20.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Physical, false, ARMOR_HALVING, &none), 10.0);
+        assert_eq!(
+            resolve_damage(20.0, DamageType::Physical, false, ARMOR_HALVING * 3.0, &none),
+            5.0
+        );
+        assert_eq!(resolve_damage(20.0, DamageType::Physical, false, -10.0, &none), 20.0);
+    }
+
+    #[test]
+    fn armor_ignores_other_damage_types() {
+        let none = Resistances::default();
+
+        assert_eq!(resolve_damage(20.0, DamageType::Fire, false, ARMOR_HALVING, &none), 20.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Poison, false, ARMOR_HALVING, &none), 20.0);
+    }
+
+    #[test]
+    fn resistances_apply_to_their_own_type() {
+        let physical = resistances(0.5, 0.0, 0.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Physical, false, 0.0, &physical), 10.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Fire, false, 0.0, &physical), 20.0);
+
+        let fire = resistances(0.0, 0.5, 0.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Fire, false, 0.0, &fire), 10.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Poison, false, 0.0, &fire), 20.0);
+
+        let poison = resistances(0.0, 0.0, 0.25);
+        assert_eq!(resolve_damage(20.0, DamageType::Poison, false, 0.0, &poison), 15.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Physical, false, 0.0, &poison), 20.0);
+    }
+
+    #[test]
+    fn resistances_are_capped() {
+        let immune = resistances(1.0, 2.0, 1.0);
+
+        for damage_type in [DamageType::Physical, DamageType::Fire, DamageType::Poison] {
+            assert_eq!(
+                resolve_damage(20.0, damage_type, false, 0.0, &immune),
+                20.0 * (1.0 - MAX_RESISTANCE)
+            );
+        }
+    }
+
+    #[test]
+    fn negative_resistances_increase_damage() {
+        let weak = resistances(0.0, -0.5, 0.0);
+        assert_eq!(resolve_damage(20.0, DamageType::Fire, false, 0.0, &weak), 30.0);
+    }
+
+    #[test]
+    fn critical_hits_are_multiplied_before_mitigation() {
+        let none = Resistances::default();
+
+        assert_eq!(
+            resolve_damage(10.0, DamageType::Fire, true, 0.0, &none),
+            10.0 * CRITICAL_MULTIPLIER
+        );
+        assert_eq!(resolve_damage(20.0, DamageType::Physical, true, ARMOR_HALVING, &none), 15.0);
+    }
+
+    #[test]
+    fn hits_always_do_minimum_damage() {
+        assert_eq!(
+            resolve_damage(1.0, DamageType::Physical, false, 100.0, &Resistances::default()),
+            MINIMUM_DAMAGE
+        );
+        assert_eq!(
+            resolve_damage(2.0, DamageType::Fire, false, 0.0, &resistances(0.0, 1.0, 0.0)),
+            MINIMUM_DAMAGE
+        );
+    }
+
+    #[test]
+    fn zero_and_negative_amounts_do_nothing() {
+        let none = Resistances::default();
+
+        assert_eq!(resolve_damage(0.0, DamageType::Physical, false, 0.0, &none), 0.0);
+        assert_eq!(resolve_damage(0.0, DamageType::Fire, true, 0.0, &none), 0.0);
+        assert_eq!(resolve_damage(-5.0, DamageType::Physical, false, 0.0, &none), 0.0);
+        assert_eq!(
+            resolve_damage(-5.0, DamageType::Poison, true, 0.0, &resistances(0.0, 0.0, -1.0)),
+            0.0
+        );
+    }
+}
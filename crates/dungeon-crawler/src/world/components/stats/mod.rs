@@ -1,6 +1,9 @@
+use crate::map::{Resistances, RewardDefinition};
 use bevy_ecs::prelude::*;
 use std::ops::Range;
+mod damage;
 mod health;
+pub use damage::*;
 pub use health::*;
 
 #[derive(Component)]
@@ -14,7 +17,13 @@ pub struct Stats {
 
     pub dexterity: u32,
     pub dexterity_speed: f32,
+    pub dexterity_stamina: f32,
+    pub dexterity_critical: f32,
     pub speed_base: f32,
+    pub stamina: f32,
+
+    pub armor: f32,
+    pub resistances: Resistances,
 
     pub experience: u32,
     pub health: health::Health,
@@ -27,15 +36,22 @@ impl Stats {
         let level = get_level(experience) - 1;
         let vitality = vitality + level * 2;
         let vitality_health = 2.0;
+        let dexterity = dexterity + level;
+        let dexterity_stamina = 4.0;
 
         Self {
             strength: strength + level * 2,
             strength_damage: 0.05,
             vitality,
             vitality_health: 2.0,
-            dexterity: dexterity + level,
+            dexterity,
             dexterity_speed: 0.02,
+            dexterity_stamina,
+            dexterity_critical: 0.005,
             speed_base: 0.4,
+            stamina: dexterity as f32 * dexterity_stamina,
+            armor: 0.0,
+            resistances: Resistances::default(),
             damage_base: 0.5..1.2,
             experience,
             health: Health::new(vitality as f32 * vitality_health),
@@ -43,12 +59,28 @@ impl Stats {
         }
     }
 
+    pub fn with_defense(mut self, armor: f32, resistances: Resistances) -> Self {
+        self.armor = armor;
+        self.resistances = resistances;
+        self
+    }
+
     pub fn get_base_health(&self) -> f32 {
         self.vitality as f32 * self.vitality_health
     }
 
+    pub fn get_base_stamina(&self) -> f32 {
+        self.dexterity as f32 * self.dexterity_stamina
+    }
+
+    /// Attacks slow down by up to 60% once stamina drops below a quarter.
     pub fn get_attack_time(&self) -> f32 {
-        self.speed_base / (self.dexterity as f32 * self.dexterity_speed)
+        let exhaustion = (1.0 - self.stamina / (self.get_base_stamina() * 0.25)).clamp(0.0, 1.0);
+        self.speed_base / (self.dexterity as f32 * self.dexterity_speed) * (1.0 + exhaustion * 0.6)
+    }
+
+    pub fn use_stamina(&mut self, amount: f32) {
+        self.stamina = (self.stamina - amount).max(0.0);
     }
 
     pub fn get_attack_damage(&self) -> Range<f32> {
@@ -56,6 +88,10 @@ impl Stats {
         (self.damage_base.start + additional_damage)..(self.damage_base.end + additional_damage)
     }
 
+    pub fn get_critical_chance(&self) -> f32 {
+        self.dexterity as f32 * self.dexterity_critical
+    }
+
     pub fn get_recovery_time(&self) -> f32 {
         (self.speed_base * 0.4) / (self.dexterity as f32 * self.dexterity_speed * 0.7)
     }
@@ -68,6 +104,20 @@ impl Stats {
         get_level(self.experience)
     }
 
+    /// Hands out loot rewards, levelling up when the experience is enough.
+    pub fn add_reward(&mut self, reward: &RewardDefinition) {
+        let level = self.get_level();
+        self.experience += reward.experience;
+
+        if reward.health != 0.0 {
+            self.health.changes.push(HealthChange::new(reward.health, HealthChangeType::Once));
+        }
+
+        if self.get_level() > level {
+            self.level_up();
+        }
+    }
+
     pub fn level_up(&mut self) {
         self.vitality += 2;
         self.strength += 2;
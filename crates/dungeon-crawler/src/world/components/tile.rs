@@ -1,9 +1,14 @@
-use bevy_ecs::prelude::Component;
+use super::{Boss, Interactable};
+use crate::map::{DestructibleDefinition, HazardDefinition, HostileDefinition, Role};
+use bevy_ecs::prelude::{Component, Entity};
 use cgmath::*;
-use engine::{bounding_box::BoundingBox, collision::Polygon};
+use engine::{bounding_box::BoundingBox, collision::Polygon, ecs::components::AnimationGraph};
+use std::sync::Arc;
 
+#[derive(Clone, Copy)]
 pub enum TileState {
-    Active,
+    /// In view, the tile model with its decor hangs from the given entity.
+    Active(Entity),
     Destroyed,
 }
 
@@ -36,28 +41,85 @@ pub struct Decor {
     pub rotation: f32,
     pub position: Vector3<f32>,
     pub collisions: Vec<Polygon>,
+    pub destructible: Option<DestructibleDefinition>,
+    pub broken_model: Option<engine::ecs::components::Model>,
+    pub destroyed: bool,
 }
 
+pub struct TileDoor {
+    pub model: engine::ecs::components::Model,
+    pub collisions: Vec<Polygon>,
+    pub position: Vector3<f32>,
+    pub rotation: f32,
+    pub lock: Option<usize>,
+    /// Lever doors are only operated by their levers.
+    pub lever: bool,
+}
+
+pub struct TileKey {
+    pub lock: usize,
+    pub position: Vector3<f32>,
+    pub model: Option<engine::ecs::components::Model>,
+}
+
+pub struct TileHazard {
+    pub definition: HazardDefinition,
+    pub position: Vector3<f32>,
+    pub rotation: f32,
+    /// Start of the cycle in seconds, so neighbouring traps do not trigger in sync.
+    pub offset: f32,
+    pub model: Option<engine::ecs::components::Model>,
+    pub emitters: Vec<DecorEmitter>,
+}
+
+/// Chests, levers, shrines and the like.
+pub struct TileObject {
+    pub model: engine::ecs::components::Model,
+    pub collisions: Vec<Polygon>,
+    pub position: Vector3<f32>,
+    pub rotation: f32,
+    pub interactable: Interactable,
+}
+
+#[derive(Clone)]
 pub struct Hostile {
+    pub definition: HostileDefinition,
     pub model: engine::ecs::components::Model,
+    pub animation_graph: Arc<AnimationGraph>,
     pub collider: Vec<Polygon>,
-    pub health: f32,
     pub position: Vector3<f32>,
+    pub route: Vec<Vector3<f32>>,
+    pub boss: Option<Boss>,
 }
 
 #[derive(Component)]
 pub struct Tile {
     pub model: engine::ecs::components::Model,
     pub state: TileState,
+    /// Hostiles, doors, keys and the like are spawned the first time the tile
+    /// comes into view and live on their own after that.
+    pub populated: bool,
+    pub role: Role,
     pub center: Vector3<f32>,
     pub rotation: f32,
     pub decor: Vec<Decor>,
     pub bounding_box: BoundingBox,
     pub collisions: Vec<Polygon>,
     pub hostiles: Vec<Hostile>,
+    pub doors: Vec<TileDoor>,
+    pub keys: Vec<TileKey>,
+    pub objects: Vec<TileObject>,
+    pub hazards: Vec<TileHazard>,
 }
 
 impl Tile {
+    pub fn contains(&self, position: Vector3<f32>) -> bool {
+        position.x >= self.bounding_box.min.x
+            && position.x <= self.bounding_box.max.x
+            && position.z >= self.bounding_box.min.z
+            && position.z <= self.bounding_box.max.z
+    }
+
     pub fn new(
         model: engine::ecs::components::Model,
         collisions: Vec<Polygon>,
@@ -73,6 +135,8 @@ impl Tile {
             model,
             collisions,
             state: TileState::Destroyed,
+            populated: false,
+            role: Role::Normal,
             center,
             bounding_box: BoundingBox {
                 min: point3(center.x - h_size, 0.0, center.z - h_size),
@@ -81,6 +145,10 @@ impl Tile {
             rotation,
             decor,
             hostiles,
+            doors: vec![],
+            keys: vec![],
+            objects: vec![],
+            hazards: vec![],
         }
     }
 }
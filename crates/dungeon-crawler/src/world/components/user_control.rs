@@ -6,11 +6,22 @@ use fxhash::FxHashMap;
 pub enum UiActionCode {
     Attack,
     Health,
+    Interact,
+    Dodge,
 }
 
 #[derive(Component, Default)]
 pub struct UserControl {
     pub ui_actions: FxHashMap<UiActionCode, PressState>,
+    /// Interact was held in the last update, interactions only fire on a new press.
+    pub interacting: bool,
+    pub dodging: bool,
+    /// Seconds until the next dodge.
+    pub dodge_cooldown: f32,
+    /// Attack was pressed near the end of the current one, the combo continues as soon as it is over.
+    pub attack_buffered: bool,
+    /// Ran out of stamina, no running until it has recovered a bit.
+    pub exhausted: bool,
 }
 
 impl UserControl {
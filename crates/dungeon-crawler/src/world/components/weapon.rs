@@ -1,10 +1,94 @@
+use crate::map::{ComboStepDefinition, DamageType, WeaponDefinition, WeaponShape};
 use bevy_ecs::prelude::*;
+use engine::collision::HitShape;
 use std::ops::Range;
 
+/// Seconds after an attack within which the next one continues the combo.
+const COMBO_RESET: f32 = 0.6;
+
 #[derive(Component)]
 pub struct Weapon {
     pub damage: Range<f32>,
+    pub damage_type: DamageType,
     pub distance: f32,
     pub radius: f32,
     pub time: f32,
+    pub shape: WeaponShape,
+    /// Width of arc swings in radians.
+    pub angle: f32,
+    pub max_targets: Option<usize>,
+    pub line_of_sight: bool,
+    pub knockback: f32,
+    pub hit_stop: u32,
+    pub combo: Vec<ComboStepDefinition>,
+    /// Current step in the combo.
+    pub step: usize,
+}
+
+impl Weapon {
+    pub fn new(definition: &WeaponDefinition) -> Self {
+        Self {
+            damage: definition.damage[0]..definition.damage[1],
+            damage_type: definition.damage_type,
+            distance: definition.distance,
+            radius: definition.radius,
+            time: definition.time,
+            shape: definition.shape,
+            angle: definition.angle.to_radians(),
+            max_targets: definition.max_targets,
+            line_of_sight: definition.line_of_sight,
+            knockback: definition.knockback,
+            hit_stop: definition.hit_stop,
+            combo: get_combo(&definition.combo),
+            step: 0,
+        }
+    }
+
+    pub fn reach(&self, attacker_radius: f32, target_radius: f32) -> f32 {
+        attacker_radius + self.distance + self.radius + target_radius
+    }
+
+    /// Moves on to the next step when the last attack ended less than `COMBO_RESET` ago, otherwise starts over.
+    pub fn advance_combo(&mut self, since_attack: Option<f32>) {
+        self.step = match since_attack {
+            Some(since_attack) if since_attack < COMBO_RESET => (self.step + 1) % self.combo.len(),
+            _ => 0,
+        };
+    }
+
+    pub fn combo_step(&self) -> &ComboStepDefinition {
+        &self.combo[self.step]
+    }
+
+    /// The area an attack covers, seen from the center of the wielder.
+    pub fn hit_shape(&self, attacker_radius: f32) -> HitShape {
+        let distance = attacker_radius + self.distance;
+
+        match self.shape {
+            WeaponShape::Circle => HitShape::Circle {
+                distance,
+                radius: self.radius,
+            },
+            WeaponShape::Arc => HitShape::Arc {
+                radius: distance + self.radius,
+                angle: self.angle,
+            },
+            WeaponShape::Line => HitShape::Line {
+                length: distance + self.radius,
+                width: self.radius * 2.0,
+            },
+        }
+    }
+}
+
+pub fn get_combo(combo: &[ComboStepDefinition]) -> Vec<ComboStepDefinition> {
+    if combo.is_empty() {
+        vec![ComboStepDefinition {
+            animation: "attack".to_string(),
+            damage: 1.0,
+            activation: 0.25,
+        }]
+    } else {
+        combo.to_vec()
+    }
 }
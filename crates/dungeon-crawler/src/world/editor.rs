@@ -0,0 +1,157 @@
+use super::{components, World};
+use crate::config;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::DespawnRecursiveExt;
+use cgmath::*;
+use engine::ecs::resources::{input::mouse::PressState, Camera, Input};
+use winit::event::VirtualKeyCode;
+
+impl World {
+    pub fn editor_keyboard(&mut self, key: VirtualKeyCode) {
+        let control = {
+            let input = self.components.get_resource::<Input>().unwrap();
+            input.is_pressed(VirtualKeyCode::LControl) || input.is_pressed(VirtualKeyCode::RControl)
+        };
+
+        if let Some(editor) = &mut self.editor {
+            match key {
+                VirtualKeyCode::S if control => editor.save(),
+                VirtualKeyCode::Z => editor.cycle_brush(-1),
+                VirtualKeyCode::X => editor.cycle_brush(1),
+                VirtualKeyCode::T => editor.rotate(),
+                VirtualKeyCode::Delete | VirtualKeyCode::Back => editor.delete(),
+                VirtualKeyCode::PageUp => editor.cycle_variant(-1),
+                VirtualKeyCode::PageDown => editor.cycle_variant(1),
+                VirtualKeyCode::N => editor.add_variant(),
+                VirtualKeyCode::C => editor.duplicate_variant(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Points the editor at the grid cell under the mouse and respawns the
+    /// edited tile whenever something changed, so lights and emitters show live.
+    pub fn update_editor(&mut self, engine: &mut engine::Engine, over_ui: bool) {
+        let (cell, clicked) = {
+            let input = self.components.get_resource::<Input>().unwrap();
+            let camera = self.components.get_resource::<Camera>().unwrap();
+            let viewport = vec2(engine.ctx.viewport.width as f32, engine.ctx.viewport.height as f32);
+
+            match input.mouse.get(&0) {
+                Some(button) => (
+                    button.position.and_then(|position| pick_cell(camera, position, viewport)),
+                    button.state == PressState::Pressed(false),
+                ),
+                None => (None, false),
+            }
+        };
+
+        let editor = match &mut self.editor {
+            Some(editor) => editor,
+            None => return,
+        };
+
+        if !over_ui {
+            editor.hover(cell);
+
+            if clicked {
+                editor.click();
+            }
+        }
+
+        if editor.rebuild {
+            editor.rebuild = false;
+            self.respawn_edited_tile(engine);
+        }
+    }
+
+    fn respawn_edited_tile(&mut self, engine: &mut engine::Engine) {
+        let (editor, resources) = match (&self.editor, &self.resources) {
+            (Some(editor), Some(resources)) => (editor, resources),
+            _ => return,
+        };
+
+        // Release the instances of the old tile, the tile system only ever spawns new ones
+        let mut model_keys = vec![];
+        let mut tiles = vec![];
+        for (entity, tile) in self.components.query::<(Entity, &components::Tile)>().iter(&self.components) {
+            model_keys.push(tile.model.key.clone());
+
+            for decor in tile.decor.iter() {
+                engine.ctx.model_instances.remove(&decor.model.key);
+                if let Some(broken_model) = &decor.broken_model {
+                    engine.ctx.model_instances.remove(&broken_model.key);
+                }
+                for emitter in decor.emitters.iter() {
+                    engine.ctx.emitter_instances.remove(&emitter.emitter_id);
+                }
+            }
+
+            tiles.push(entity);
+        }
+
+        let rendered: Vec<Entity> = self
+            .components
+            .query::<(Entity, &engine::ecs::components::Model)>()
+            .iter(&self.components)
+            .filter(|(_, model)| model_keys.contains(&model.key))
+            .map(|(entity, _)| entity)
+            .collect();
+
+        for key in model_keys.iter() {
+            engine.ctx.model_instances.remove(key);
+        }
+
+        for entity in tiles.into_iter().chain(rendered) {
+            self.components.entity_mut(entity).despawn_recursive();
+        }
+
+        resources
+            .map
+            .single_tile(engine, &mut self.components, &editor.piece, &editor.preview());
+
+        let selected = editor.selected.map(|index| editor.decor()[index].pos);
+        for (mut text, transform) in self
+            .components
+            .query::<(&mut engine::ecs::components::Text, &engine::ecs::components::Transform)>()
+            .iter_mut(&mut self.components)
+        {
+            let position = transform.translation.current / config::GRID_DIST;
+            let cell = [position.x.round() as i32, position.z.round() as i32];
+
+            text.text = if Some(cell) == selected {
+                format!("<{},{}>", cell[0], cell[1])
+            } else if Some(cell) == editor.cursor {
+                format!("[{},{}]", cell[0], cell[1])
+            } else {
+                format!("{},{}", cell[0], cell[1])
+            };
+        }
+    }
+}
+
+/// Casts a ray from the mouse position through the camera onto the floor
+/// and returns the grid cell it hits, if it is inside the tile.
+fn pick_cell(camera: &Camera, position: Point2<f32>, viewport: Vector2<f32>) -> Option<[i32; 2]> {
+    let inverse = camera.view_proj.invert()?;
+    let x = position.x / viewport.x * 2.0 - 1.0;
+    let y = 1.0 - position.y / viewport.y * 2.0;
+
+    let near = inverse * vec4(x, y, -1.0, 1.0);
+    let far = inverse * vec4(x, y, 1.0, 1.0);
+    let near = near.truncate() / near.w;
+    let direction = far.truncate() / far.w - near;
+
+    if direction.y.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let floor = (near + direction * (-near.y / direction.y)) / config::GRID_DIST;
+    let cell = [floor.x.round() as i32, floor.z.round() as i32];
+
+    if cell.iter().all(|c| c.abs() <= config::GRID_COUNT) {
+        Some(cell)
+    } else {
+        None
+    }
+}
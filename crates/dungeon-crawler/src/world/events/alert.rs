@@ -0,0 +1,10 @@
+use bevy_ecs::prelude::*;
+use cgmath::*;
+
+/// Sent when a hostile becomes aggressive so that nearby allies can join in.
+pub struct Alert {
+    pub entity: Entity,
+    pub team: usize,
+    pub position: Vector3<f32>,
+    pub target: Vector3<f32>,
+}
@@ -0,0 +1,9 @@
+use crate::world::components::InteractAction;
+use bevy_ecs::prelude::*;
+
+/// Sent when the player uses the interactable in front of them.
+pub struct Interaction {
+    pub actor: Entity,
+    pub target: Entity,
+    pub action: InteractAction,
+}
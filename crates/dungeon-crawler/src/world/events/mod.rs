@@ -0,0 +1,5 @@
+mod alert;
+mod interaction;
+
+pub use alert::Alert;
+pub use interaction::Interaction;
@@ -1,6 +1,8 @@
 use crate::{config, map};
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 pub mod components;
+mod editor;
+pub mod events;
 pub mod resources;
 pub mod systems;
 use bevy_ecs::prelude::*;
@@ -18,6 +20,7 @@ pub enum GameState {
 pub struct Resources {
     pub map: map::Map,
     pub character: engine::model::GltfModel,
+    pub character_animations: Arc<engine::ecs::components::AnimationGraph>,
 }
 
 pub struct World {
@@ -26,6 +29,9 @@ pub struct World {
     pub post_schedule: Schedule,
     pub game_state: GameState,
     pub resources: Option<Resources>,
+    pub editor: Option<map::Editor>,
+    /// Floor of the dungeon that `load_resources` builds the map for, starting at 1.
+    pub floor: usize,
 }
 
 impl World {
@@ -37,13 +43,29 @@ impl World {
             "update",
             SystemStage::parallel()
                 .with_system(systems::flicker)
-                .with_system(systems::user_control)
+                .with_system(Events::<events::Interaction>::update_system.before("user_control"))
+                .with_system(systems::user_control.label("user_control"))
+                .with_system(Events::<engine::ecs::events::AnimationEvent>::update_system.before("actions"))
                 .with_system(systems::actions.label("actions"))
-                .with_system(systems::collision.label("collision").after("actions"))
+                .with_system(systems::steering.label("steering").after("actions").after("behaviour"))
+                .with_system(systems::door.after("user_control").before("collision"))
+                .with_system(systems::chest.after("user_control").before("health"))
+                .with_system(systems::hazard.after("user_control").before("health"))
+                .with_system(systems::pickup)
+                .with_system(systems::collision.label("collision").after("steering"))
                 .with_system(systems::damage.after("actions"))
+                .with_system(systems::hit_stop)
                 .with_system(systems::movement.after("collision"))
-                .with_system(systems::aggression)
-                .with_system(systems::health),
+                .with_system(Events::<events::Alert>::update_system.before("aggression"))
+                .with_system(systems::aggression.label("aggression"))
+                .with_system(systems::alert.label("alert").after("aggression"))
+                .with_system(systems::behaviour.label("behaviour").after("alert"))
+                .with_system(systems::shrine.after("user_control").before("health"))
+                .with_system(systems::buff)
+                .with_system(systems::stamina.after("user_control"))
+                .with_system(systems::health.label("health"))
+                .with_system(systems::destructible.after("health"))
+                .with_system(systems::boss.after("health")),
         );
 
         let mut post_schedule = Schedule::default();
@@ -61,7 +83,9 @@ impl World {
             schedule,
             post_schedule,
             resources: None,
+            editor: None,
             game_state: GameState::Loading,
+            floor: 1,
         }
     }
 
@@ -76,31 +100,64 @@ impl World {
                 .get("character")
                 .expect("Could not find character collider!");
 
-            self.components.spawn((
-                engine::ecs::components::Animations::new("base", "idle", engine::ecs::components::AnimationStatus::Repeat),
-                character_model,
-                components::Collision::new(collider.clone()),
-                engine::ecs::components::Transform::from_translation_scale(vec3(0.0, 0.0, 0.0), 0.01),
-                components::Movement::new(15.0),
-                components::ActionExecutor::new(),
-                components::Stats::new(15, 15, 15, 0, config::TEAM_FRIENDLY),
-                components::Weapon {
-                    damage: 2.0..7.0,
-                    distance: 0.5,
-                    radius: 0.25,
-                    time: 1.0,
-                },
-                components::UserControl::default(),
-                engine::ecs::components::SoundEffects::default(),
-                engine::ecs::components::Render { cull_frustum: false },
-                engine::ecs::components::Shadow,
-                engine::ecs::components::Follow,
-                engine::ecs::components::Light::new(vec3(1.0, 0.94, 0.88), 0.5, 7.0, vec3(0.0, 3.0, 0.0), 0.0),
-                components::Target,
-            ));
+            self.components
+                .spawn((
+                    engine::ecs::components::Animations::from_graph(resources.character_animations.clone()),
+                    character_model,
+                    components::Collision::new(collider.clone()),
+                    engine::ecs::components::Transform::from_translation_scale(vec3(0.0, 0.0, 0.0), 0.01),
+                    components::Movement::new(15.0),
+                    components::ActionExecutor::new(),
+                    components::Stats::new(15, 15, 15, 0, config::TEAM_FRIENDLY).with_defense(2.0, map::Resistances::default()),
+                    components::Weapon {
+                        damage: 2.0..7.0,
+                        damage_type: map::DamageType::Physical,
+                        distance: 0.5,
+                        radius: 0.25,
+                        time: 1.0,
+                        shape: map::WeaponShape::Arc,
+                        angle: 110.0_f32.to_radians(),
+                        max_targets: Some(3),
+                        line_of_sight: true,
+                        knockback: 0.4,
+                        hit_stop: 0,
+                        combo: vec![
+                            map::ComboStepDefinition {
+                                animation: "attack".to_string(),
+                                damage: 1.0,
+                                activation: 0.25,
+                            },
+                            map::ComboStepDefinition {
+                                animation: "attack".to_string(),
+                                damage: 1.2,
+                                activation: 0.25,
+                            },
+                            map::ComboStepDefinition {
+                                animation: "attack".to_string(),
+                                damage: 1.6,
+                                activation: 0.4,
+                            },
+                        ],
+                        step: 0,
+                    },
+                    components::UserControl::default(),
+                    engine::ecs::components::SoundEffects::default(),
+                    engine::ecs::components::Render { cull_frustum: false },
+                    engine::ecs::components::Shadow,
+                    engine::ecs::components::Follow,
+                    engine::ecs::components::Light::new(vec3(1.0, 0.94, 0.88), 0.5, 7.0, vec3(0.0, 3.0, 0.0), 0.0),
+                    components::Target,
+                ))
+                .insert(components::Inventory::default());
 
             if let Some((tile_name, variant)) = map::edit_mode() {
-                resources.map.single_tile(engine, &mut self.components, &tile_name, variant);
+                // The tile itself is spawned by the editor on its next update
+                let editor = self
+                    .editor
+                    .get_or_insert_with(|| map::Editor::new(&resources.map.theme, &tile_name, variant, resources.map.decor_catalogue()));
+                editor.rebuild = true;
+
+                resources.map.add_grid(&mut self.components, Vector3::zero());
             } else {
                 resources.map.generate(&mut self.components, engine);
             }
@@ -156,8 +213,10 @@ impl World {
 
     pub fn load_resources(&mut self, ctx: &engine::Context) {
         let start = Instant::now();
-        let character = engine::load_model(ctx, "models/character.glb");
-        let map = map::Map::new(ctx, 42312, 3);
+        let mut character = engine::load_model(ctx, "models/character.glb");
+        let character_animations = Arc::new(engine::ecs::components::AnimationGraph::load("animations/character.json"));
+        character.add_bone_masks(character_animations.layers().filter_map(|layer| layer.mask.as_deref()));
+        let map = map::Map::new(ctx, 42312, 3, self.floor);
 
         let mut sound_effects = self
             .components
@@ -168,10 +227,13 @@ impl World {
         sound_effects.load(&map.sound_effects);
         sound_effects.volume = ctx.settings.audio_effects;
 
-        self.set_sounds(ctx);
-
         println!("Load resources {} ms", start.elapsed().as_millis());
-        self.resources = Some(Resources { map, character });
+        self.resources = Some(Resources {
+            map,
+            character,
+            character_animations,
+        });
+        self.set_sounds(ctx);
     }
 
     pub fn set_sounds(&mut self, ctx: &engine::Context) {
@@ -182,7 +244,9 @@ impl World {
                 .unwrap();
 
             sound_ambience.volume = ctx.settings.audio_ambient;
-            sound_ambience.play("ambience");
+            if let Some(resources) = &self.resources {
+                sound_ambience.play(&resources.map.theme.ambience);
+            }
         }
 
         {
@@ -205,6 +269,9 @@ pub fn setup_world(ctx: &engine::Context) -> bevy_ecs::world::World {
     components.insert_non_send_resource(engine::ecs::resources::SoundAmbience::default());
     components.insert_resource(engine::ecs::resources::Input::default());
     components.insert_resource(resources::Fps::default());
+    components.insert_resource(Events::<events::Alert>::default());
+    components.insert_resource(Events::<events::Interaction>::default());
+    components.insert_resource(Events::<engine::ecs::events::AnimationEvent>::default());
 
     components
 }
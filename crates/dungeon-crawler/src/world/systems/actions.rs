@@ -1,13 +1,19 @@
 use crate::world::{components::Action, *};
 use bevy_ecs::prelude::*;
 use cgmath::*;
-use engine::{
-    ecs::components::{AnimationSpeed, AnimationStatus},
-    utils,
-};
+use engine::{ecs::events::AnimationEvent, utils};
+
+const DODGE_DISTANCE: f32 = 3.0;
+
+/// Fastest an attacker can keep moving while swinging.
+const ATTACK_VELOCITY: f32 = 0.05;
+
+/// Animation event at which an attack deals its damage.
+const HIT_EVENT: &str = "hit";
 
 pub fn actions(
     mut commands: Commands,
+    mut animation_events: EventReader<AnimationEvent>,
     mut query: Query<(
         Entity,
         &components::Stats,
@@ -16,40 +22,66 @@ pub fn actions(
         &mut engine::ecs::components::Animations,
         &mut components::ActionExecutor,
         Option<&components::Weapon>,
+        Option<&components::Collision>,
+        Option<&engine::ecs::components::Model>,
     )>,
 ) {
-    for (entity, stats, transform, mut movement, mut animation, mut action, weapon) in query.iter_mut() {
-        match &action.get() {
+    let hits: Vec<(Entity, String)> = animation_events
+        .iter()
+        .filter(|event| event.name == HIT_EVENT)
+        .map(|event| (event.entity, event.animation.clone()))
+        .collect();
+
+    for (entity, stats, transform, mut movement, mut animation, mut action, weapon, collision, model) in query.iter_mut() {
+        let current = action.get();
+
+        match current {
             Action::None => {
                 movement.velocity = vec1(movement.velocity).lerp(vec1(movement.target_velocity), 0.1).x;
                 if movement.velocity.abs() <= 0.004 {
                     movement.velocity = 0.0;
-                    animation.set_animation("base", "idle", AnimationSpeed::Original, AnimationStatus::Repeat);
-                } else if movement.velocity > 0.08 {
-                    animation.set_animation("base", "run", AnimationSpeed::Speed(1.0), AnimationStatus::Repeat);
-                } else {
-                    animation.set_animation("base", "walk", AnimationSpeed::Speed(1.0), AnimationStatus::Repeat);
                 }
             }
             Action::Attack => {
-                movement.velocity *= 0.85;
+                let target_velocity = movement.target_velocity.min(ATTACK_VELOCITY);
+                movement.velocity = vec1(movement.velocity).lerp(vec1(target_velocity), 0.15).x;
 
-                if movement.velocity < 0.01 {
-                    animation.set_animation("base", "attack", AnimationSpeed::Length(action.length), AnimationStatus::Repeat);
-                }
+                // Models with a hit event in the attack land the blow on it, only when it comes from the animation of
+                // the current step so events from other layers are left alone. Without one the activation time of the
+                // combo step decides.
+                let strike = match (weapon, model) {
+                    (Some(weapon), Some(model)) if model.has_animation_event(&weapon.combo_step().animation, HIT_EVENT) => {
+                        let step = &weapon.combo_step().animation;
+                        action.execute_once(hits.iter().any(|(hit, animation)| *hit == entity && animation == step))
+                    }
+                    _ => action.should_execute(),
+                };
 
-                if action.should_execute() {
+                if strike {
                     if let Some(weapon) = weapon {
                         let dir = vec3(movement.direction.sin(), 0.0, movement.direction.cos());
+                        let attacker_radius = collision.map_or(0.0, |c| c.radius);
+                        let distance = attacker_radius + weapon.distance;
                         let damage_base = stats.get_attack_damage();
+                        let multiplier = weapon.combo_step().damage;
 
                         commands.spawn((
                             components::Attack {
+                                attacker: entity,
+                                origin: transform.translation.current,
+                                direction: vec2(dir.x, dir.z),
                                 team: stats.team,
-                                damage: (damage_base.start * weapon.damage.start)..(damage_base.end * weapon.damage.end),
-                                radius: weapon.radius,
+                                damage: (damage_base.start * weapon.damage.start * multiplier)
+                                    ..(damage_base.end * weapon.damage.end * multiplier),
+                                damage_type: weapon.damage_type,
+                                critical_chance: stats.get_critical_chance(),
+                                shape: weapon.hit_shape(attacker_radius),
+                                max_targets: weapon.max_targets,
+                                line_of_sight: weapon.line_of_sight,
+                                knockback: weapon.knockback,
+                                hit_stop: weapon.hit_stop,
                             },
-                            engine::ecs::components::Transform::from_translation(transform.translation.current + dir * weapon.distance),
+                            engine::ecs::components::Transform::from_translation(transform.translation.current + dir * distance),
                         ));
                     }
                 }
@@ -59,20 +91,149 @@ pub fn actions(
 
                 if action.should_execute() {
                     utils::vibrate(engine::config::VIBRATION_LENGTH * 2.0);
-                    animation.set_animation("base", "hit", AnimationSpeed::Length(action.length), AnimationStatus::Default);
                 }
             }
+            Action::Dodge => {
+                movement.velocity = DODGE_DISTANCE / (action.length * config::UPDATES_PER_SECOND);
+            }
             Action::Death => {
                 movement.velocity *= 0.0;
 
                 if action.should_execute() {
                     utils::vibrate(engine::config::VIBRATION_LENGTH * 2.0);
                     commands.entity(entity).remove::<components::Movement>();
-                    animation.set_animation("base", "death", AnimationSpeed::Original, AnimationStatus::Default);
                 }
             }
         }
 
-        movement.to = vec3(movement.direction.sin(), 0.0, movement.direction.cos()) * movement.velocity;
+        // The animation graph of the model picks the animation from these
+        animation.set_parameter("action", current.name());
+        animation.set_parameter("action_length", action.length);
+        animation.set_parameter("velocity", movement.velocity);
+        if let Some(weapon) = weapon {
+            animation.set_parameter("attack_animation", weapon.combo_step().animation.as_str());
+        }
+
+        movement.to = vec3(movement.direction.sin(), 0.0, movement.direction.cos()) * movement.velocity + movement.knockback;
+        movement.knockback = if movement.knockback.magnitude() > 0.001 {
+            movement.knockback * components::KNOCKBACK_DAMPING
+        } else {
+            Vector3::zero()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::event::Events;
+    use engine::{ecs::components::AnimationStatus, model::animation::EventKeyframe};
+    use std::time::Duration;
+
+    fn weapon() -> components::Weapon {
+        let definition = serde_json::from_str(
+            r#"{ "damage": [1.0, 2.0], "distance": 0.5, "radius": 0.5, "time": 1.0, "combo": [{ "animation": "slash", "damage": 1.0, "activation": 0.0 }] }"#,
+        )
+        .unwrap();
+
+        components::Weapon::new(&definition)
+    }
+
+    fn model(events: &[(&str, &str)]) -> engine::ecs::components::Model {
+        let mut model = engine::ecs::components::Model::new("model");
+
+        for (animation, name) in events {
+            model
+                .animation_events
+                .entry(animation.to_string())
+                .or_default()
+                .push(EventKeyframe {
+                    name: name.to_string(),
+                    timestamp: 0.5,
+                });
+        }
+
+        model
+    }
+
+    /// Spawns an attacker halfway through the first step of its combo.
+    fn attacker(world: &mut World, model: engine::ecs::components::Model) -> Entity {
+        // Started a moment ago, so the new executor is not still busy with its empty action
+        let mut action = components::ActionExecutor::new();
+        action.set -= Duration::from_secs(1);
+        action.set_action(Action::Attack, 10.0, 0.0);
+
+        world
+            .spawn((
+                components::Stats::new(1, 1, 1, 0, config::TEAM_FRIENDLY),
+                engine::ecs::components::Transform::from_translation(Vector3::zero()),
+                components::Movement::new(10.0),
+                engine::ecs::components::Animations::new("base", "idle", AnimationStatus::Repeat),
+                action,
+                weapon(),
+                model,
+            ))
+            .id()
+    }
+
+    fn run(world: &mut World, events: Vec<AnimationEvent>) -> usize {
+        let mut queue = world.resource_mut::<Events<AnimationEvent>>();
+        events.into_iter().for_each(|event| queue.send(event));
+
+        SystemStage::single(actions).run(world);
+        world.query::<&components::Attack>().iter(world).count()
+    }
+
+    fn event(entity: Entity, animation: &str, name: &str) -> AnimationEvent {
+        AnimationEvent {
+            entity,
+            animation: animation.into(),
+            name: name.into(),
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<AnimationEvent>>();
+        world
+    }
+
+    #[test]
+    fn attack_lands_on_the_hit_event() {
+        let mut world = world();
+        let entity = attacker(&mut world, model(&[("slash", HIT_EVENT)]));
+
+        assert_eq!(run(&mut world, vec![]), 0);
+        assert_eq!(run(&mut world, vec![event(entity, "slash", HIT_EVENT)]), 1);
+        assert_eq!(run(&mut world, vec![event(entity, "slash", HIT_EVENT)]), 1);
+    }
+
+    #[test]
+    fn hit_events_from_other_animations_are_ignored() {
+        let mut world = world();
+        let entity = attacker(&mut world, model(&[("slash", HIT_EVENT), ("wave", HIT_EVENT)]));
+
+        assert_eq!(run(&mut world, vec![event(entity, "wave", HIT_EVENT)]), 0);
+        assert_eq!(run(&mut world, vec![event(entity, "slash", "footstep")]), 0);
+    }
+
+    #[test]
+    fn hit_events_of_other_entities_are_ignored() {
+        let mut world = world();
+        let entity = attacker(&mut world, model(&[("slash", HIT_EVENT)]));
+        let other = attacker(&mut world, model(&[("slash", HIT_EVENT)]));
+
+        assert_eq!(run(&mut world, vec![event(other, "slash", HIT_EVENT)]), 1);
+        let attack = world.query::<&components::Attack>().single(&world);
+        assert_eq!(attack.attacker, other);
+        assert_ne!(attack.attacker, entity);
+    }
+
+    #[test]
+    fn attack_without_hit_event_lands_on_activation() {
+        let mut world = world();
+        attacker(&mut world, model(&[]));
+
+        assert_eq!(run(&mut world, vec![]), 1);
     }
 }
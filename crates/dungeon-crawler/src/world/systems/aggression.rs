@@ -1,43 +1,83 @@
 use crate::world::*;
 use bevy_ecs::prelude::*;
+use engine::collision::{Polygon, PolygonMethods};
 
 pub fn aggression(
     mut query: ParamSet<(
-        Query<(&components::Target, &engine::ecs::components::Transform)>,
         Query<(
+            &components::Target,
+            &engine::ecs::components::Transform,
+            Option<&components::Collision>,
+        )>,
+        Query<(
+            Entity,
             &mut components::Agressor,
-            &mut components::Movement,
-            &mut components::ActionExecutor,
             &components::Stats,
-            Option<&components::Weapon>,
             &engine::ecs::components::Transform,
         )>,
     )>,
+    attack_query: Query<(&components::Attack, &engine::ecs::components::Transform)>,
+    collision_query: Query<(&components::Collision, &engine::ecs::components::Transform), Without<components::Movement>>,
+    mut alerts: EventWriter<events::Alert>,
 ) {
-    let targets: Vec<Vector3<f32>> = query.p0().iter().map(|t| t.1.translation.current).collect();
+    let targets: Vec<(Vector3<f32>, f32)> = query
+        .p0()
+        .iter()
+        .map(|(_, t, c)| (t.translation.current, c.map_or(0.0, |c| c.radius)))
+        .collect();
+    let attacks: Vec<(usize, Vector3<f32>)> = attack_query.iter().map(|(a, t)| (a.team, t.translation.current)).collect();
+    let collisions: Vec<Polygon> = collision_query
+        .iter()
+        .flat_map(|(c, t)| {
+            c.polygons
+                .iter()
+                .map(move |p| p.transform(t.translation.current, t.rotation.current))
+        })
+        .collect();
 
-    for (mut agressor, mut movement, mut action, stats, weapon, transform) in query.p1().iter_mut() {
-        for target_transform in targets.iter() {
-            let distance = transform.translation.current.distance(*target_transform);
+    for (entity, mut agressor, stats, transform) in query.p1().iter_mut() {
+        let position = transform.translation.current;
+        let was_aggressive = agressor.is_aggressive;
+        agressor.visible_target = None;
+
+        for (target_transform, target_radius) in targets.iter() {
+            let distance = position.distance(*target_transform);
             let range = if agressor.is_aggressive {
                 agressor.end_range
             } else {
                 agressor.start_range
             };
 
-            if action.get() == components::Action::None {
-                movement.towards(target_transform - transform.translation.current);
+            let is_visible = distance < range
+                && engine::collision::check_line_of_sight(
+                    vec2(position.x, position.z),
+                    vec2(target_transform.x, target_transform.z),
+                    &collisions,
+                );
 
-                // TODO: Attack range
-                if distance < 1.0 {
-                    if let Some(weapon) = weapon {
-                        action.set_action(components::Action::Attack, weapon.time * stats.get_attack_time(), 0.25);
-                    }
-                } else if transform.translation.current.distance(*target_transform) < range {
-                    movement.velocity = 0.07;
-                    agressor.is_aggressive = true;
-                }
+            if is_visible {
+                agressor.see(*target_transform, *target_radius);
+            } else if let Some((_, attack_position)) = attacks
+                .iter()
+                .find(|(team, attack_position)| *team != stats.team && position.distance(*attack_position) < agressor.hearing_range)
+            {
+                agressor.alert(*attack_position);
             }
         }
+
+        if !was_aggressive && agressor.is_aggressive {
+            if let Some(target) = agressor.last_known_position {
+                alerts.send(events::Alert {
+                    entity,
+                    team: stats.team,
+                    position,
+                    target,
+                });
+            }
+        }
+
+        if agressor.is_aggressive && agressor.has_forgotten() {
+            agressor.calm();
+        }
     }
 }
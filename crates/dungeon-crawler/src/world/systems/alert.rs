@@ -0,0 +1,76 @@
+use crate::world::*;
+use bevy_ecs::prelude::*;
+use engine::{
+    collision::{Polygon, PolygonMethods},
+    ecs::components::Sound,
+};
+
+pub fn alert(
+    mut alerts: EventReader<events::Alert>,
+    mut query: Query<(
+        Entity,
+        &mut components::Agressor,
+        &components::Stats,
+        &engine::ecs::components::Transform,
+        Option<&mut engine::ecs::components::SoundEffects>,
+    )>,
+    collision_query: Query<(&components::Collision, &engine::ecs::components::Transform), Without<components::Movement>>,
+) {
+    let alerts: Vec<&events::Alert> = alerts.iter().collect();
+    if alerts.is_empty() {
+        return;
+    }
+
+    let collisions: Vec<Polygon> = collision_query
+        .iter()
+        .flat_map(|(c, t)| {
+            c.polygons
+                .iter()
+                .map(move |p| p.transform(t.translation.current, t.rotation.current))
+        })
+        .collect();
+
+    for alert in alerts {
+        let alert_radius = match query.get_mut(alert.entity) {
+            Ok((_, agressor, _, _, sound_effects)) => {
+                if let (Some(mut sound_effects), Some(alarm_sound)) = (sound_effects, &agressor.alarm_sound) {
+                    sound_effects.set(format!("{:?}_alarm", alert.entity), Sound::new(alarm_sound));
+                }
+
+                agressor.alert_radius
+            }
+            Err(_) => continue,
+        };
+
+        for (entity, mut agressor, stats, transform, _) in query.iter_mut() {
+            let position = transform.translation.current;
+
+            let is_alerted = entity != alert.entity
+                && !agressor.is_aggressive
+                && stats.team == alert.team
+                && position.distance(alert.position) < alert_radius
+                && is_neighbouring_tile(position, alert.position)
+                && engine::collision::check_line_of_sight(
+                    vec2(position.x, position.z),
+                    vec2(alert.position.x, alert.position.z),
+                    &collisions,
+                );
+
+            if is_alerted {
+                agressor.alert(alert.target);
+            }
+        }
+    }
+}
+
+fn is_neighbouring_tile(a: Vector3<f32>, b: Vector3<f32>) -> bool {
+    let tile = |p: Vector3<f32>| {
+        (
+            (p.x / config::TILE_SIZE).round() as i32,
+            (p.z / config::TILE_SIZE).round() as i32,
+        )
+    };
+    let ((ax, az), (bx, bz)) = (tile(a), tile(b));
+
+    (ax - bx).abs() + (az - bz).abs() <= 1
+}
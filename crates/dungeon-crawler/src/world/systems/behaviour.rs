@@ -0,0 +1,117 @@
+use crate::world::{
+    components::{Action, BehaviourState},
+    *,
+};
+use bevy_ecs::prelude::*;
+
+const PATROL_TIMEOUT: f32 = 10.0;
+
+pub fn behaviour(
+    mut query: Query<(
+        &mut components::Behaviour,
+        &mut components::Agressor,
+        &mut components::Movement,
+        &mut components::ActionExecutor,
+        &mut components::Stats,
+        Option<&mut components::Weapon>,
+        &components::Collision,
+        &engine::ecs::components::Transform,
+    )>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (mut behaviour, mut agressor, mut movement, mut action, mut stats, mut weapon, collision, transform) in query.iter_mut() {
+        if action.get() != Action::None {
+            continue;
+        }
+
+        let position = transform.translation.current;
+
+        // Leash back to spawn when the target has been pulled too far away
+        if let Some(last_known_position) = agressor.last_known_position {
+            if behaviour.spawn.distance(last_known_position) > agressor.end_range {
+                behaviour.set_state(BehaviourState::Return);
+            }
+        }
+
+        if agressor.is_aggressive && matches!(behaviour.state, BehaviourState::Idle | BehaviourState::Patrol) {
+            behaviour.set_state(BehaviourState::Chase);
+        }
+
+        match behaviour.state {
+            BehaviourState::Idle => {
+                movement.target_velocity = 0.0;
+
+                if behaviour.elapsed() > behaviour.wait_time {
+                    behaviour.patrol(&mut rng);
+                }
+            }
+            BehaviourState::Patrol => {
+                let (destination, speed) = (behaviour.destination, behaviour.walk_speed);
+
+                if move_to(&mut movement, position, destination, speed) || behaviour.elapsed() > PATROL_TIMEOUT {
+                    behaviour.idle(&mut rng);
+                }
+            }
+            BehaviourState::Chase | BehaviourState::Attack => {
+                if !agressor.is_aggressive {
+                    behaviour.set_state(BehaviourState::Return);
+                } else if let Some(target) = agressor.visible_target {
+                    movement.towards(target - position);
+
+                    match &mut weapon {
+                        Some(weapon) if position.distance(target) <= weapon.reach(collision.radius, agressor.target_radius) => {
+                            movement.target_velocity = 0.0;
+                            weapon.advance_combo(action.since_finished(Action::Attack));
+                            let activation = weapon.combo_step().activation;
+                            action.set_action(Action::Attack, weapon.time * stats.get_attack_time(), activation);
+                            behaviour.set_state(BehaviourState::Attack);
+                        }
+                        _ => {
+                            // Head for the assigned attack slot and wait there, facing the target
+                            if let Some(slot) = behaviour.slot {
+                                if move_to(&mut movement, position, slot, behaviour.chase_speed) {
+                                    movement.towards(target - position);
+                                }
+                            } else {
+                                movement.target_velocity = behaviour.chase_speed;
+                            }
+
+                            behaviour.set_state(BehaviourState::Chase);
+                        }
+                    }
+                } else if let Some(last_known_position) = agressor.last_known_position {
+                    // Keep searching where the target was last seen or heard
+                    move_to(&mut movement, position, last_known_position, behaviour.chase_speed);
+                    behaviour.set_state(BehaviourState::Chase);
+                }
+            }
+            BehaviourState::Return => {
+                agressor.calm();
+
+                if stats.health.get() < stats.get_base_health() {
+                    stats.health.changes.push(components::HealthChange::new(
+                        behaviour.regeneration / config::UPDATES_PER_SECOND,
+                        components::HealthChangeType::Once,
+                    ));
+                }
+
+                let (spawn, speed) = (behaviour.spawn, behaviour.walk_speed);
+                if move_to(&mut movement, position, spawn, speed) {
+                    behaviour.idle(&mut rng);
+                }
+            }
+        }
+    }
+}
+
+fn move_to(movement: &mut components::Movement, position: Vector3<f32>, destination: Vector3<f32>, speed: f32) -> bool {
+    if position.distance(destination) < 0.3 {
+        movement.target_velocity = 0.0;
+        return true;
+    }
+
+    movement.towards(destination - position);
+    movement.target_velocity = speed;
+    false
+}
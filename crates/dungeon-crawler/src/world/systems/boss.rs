@@ -0,0 +1,47 @@
+use crate::world::*;
+use bevy_ecs::prelude::*;
+
+pub fn boss(
+    mut commands: Commands,
+    mut query: Query<(
+        &mut components::Boss,
+        &mut components::Stats,
+        &mut components::Weapon,
+        &mut components::Behaviour,
+        &components::Agressor,
+    )>,
+) {
+    for (mut boss, mut stats, mut weapon, mut behaviour, agressor) in query.iter_mut() {
+        let health = stats.health.get() / stats.get_base_health();
+        if health <= 0.0 {
+            continue;
+        }
+
+        while let Some(phase) = boss.next_phase(health) {
+            let definition = &phase.definition;
+
+            if let Some(phase_weapon) = &definition.weapon {
+                *weapon = components::Weapon::new(phase_weapon);
+            }
+
+            if let Some(dexterity) = definition.dexterity {
+                stats.dexterity = dexterity;
+            }
+
+            if let Some(chase_speed) = definition.chase_speed {
+                behaviour.chase_speed = chase_speed;
+            }
+
+            for minion in phase.minions.iter() {
+                let minion_entity = systems::spawn_hostile(&mut commands, minion);
+
+                // Minions join the fight straight away instead of idling in the room
+                if let Some(target) = agressor.last_known_position {
+                    let mut minion_agressor = components::Agressor::new(&minion.definition.behaviour);
+                    minion_agressor.alert(target);
+                    commands.entity(minion_entity).insert(minion_agressor);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::{config, world::components};
+use bevy_ecs::prelude::*;
+
+pub fn buff(mut commands: Commands, mut query: Query<(Entity, &mut components::Stats, &mut components::Buff)>) {
+    for (entity, mut stats, mut buff) in query.iter_mut() {
+        buff.remaining -= 1.0 / config::UPDATES_PER_SECOND;
+
+        if buff.remaining <= 0.0 {
+            stats.strength -= buff.strength;
+            stats.dexterity -= buff.dexterity;
+            commands.entity(entity).remove::<components::Buff>();
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use super::destructible::spawn_loot;
+use crate::world::*;
+use bevy_ecs::prelude::*;
+
+pub fn chest(
+    mut commands: Commands,
+    mut interactions: EventReader<events::Interaction>,
+    transform_query: Query<&engine::ecs::components::Transform>,
+) {
+    for interaction in interactions.iter() {
+        let loot = match &interaction.action {
+            components::InteractAction::Chest(loot) => loot,
+            _ => continue,
+        };
+
+        // Dropped between the chest and whoever opened it, so it does not end up inside the chest
+        if let (Ok(chest), Ok(actor)) = (transform_query.get(interaction.target), transform_query.get(interaction.actor)) {
+            let position = chest.translation.current.lerp(actor.translation.current, 0.5);
+            spawn_loot(&mut commands, loot, position);
+        }
+
+        // Opened chests stay where they are, empty
+        commands.entity(interaction.target).remove::<components::Interactable>();
+    }
+}
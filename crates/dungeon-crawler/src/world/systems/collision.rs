@@ -1,5 +1,6 @@
 use crate::world::*;
 use bevy_ecs::prelude::*;
+use cgmath::*;
 use engine::collision::{Polygon, PolygonMethods};
 
 pub fn collision(
@@ -11,7 +12,7 @@ pub fn collision(
     collision_query: Query<(&components::Collision, &engine::ecs::components::Transform)>,
 ) {
     movement_query.par_for_each_mut(1, |(mut movement, collider, transform)| {
-        if movement.velocity == 0.0 {
+        if movement.to.is_zero() {
             return;
         }
 
@@ -1,52 +1,138 @@
 use crate::world::components;
 use bevy_ecs::prelude::*;
 use cgmath::*;
-use engine::collision::PolygonMethods;
+use engine::collision::{Polygon, PolygonMethods};
 use rand::Rng;
 
+/// Part of the roll, from its start, during which attacks miss.
+const DODGE_INVULNERABLE: f32 = 0.7;
+/// Critical hits always stop for a moment, on top of the weapon's own hit-stop.
+const CRITICAL_HIT_STOP: u32 = 3;
+
 pub fn damage(
     mut commands: Commands,
-    attack_query: Query<(Entity, &components::Attack, &engine::ecs::components::Transform)>,
+    attack_query: Query<(Entity, &components::Attack)>,
+    wall_query: Query<(&components::Collision, &engine::ecs::components::Transform), Without<components::Stats>>,
+    actor_query: Query<(), With<components::ActionExecutor>>,
     mut target_query: Query<(
+        Entity,
         &mut components::Stats,
         &components::Collision,
         &engine::ecs::components::Transform,
+        Option<&components::ActionExecutor>,
+        Option<&mut components::Movement>,
     )>,
 ) {
     let mut rng = rand::thread_rng();
+    let mut walls: Option<Vec<Polygon>> = None;
 
-    for (entity, attack, attack_transform) in attack_query.iter() {
-        let attack_center = vec2(attack_transform.translation.current.x, attack_transform.translation.current.z);
+    for (entity, attack) in attack_query.iter() {
+        let origin = vec2(attack.origin.x, attack.origin.z);
 
-        for (mut target_stats, target, target_transform) in target_query.iter_mut() {
-            // Avoid friendly fire
-            if target_stats.team == attack.team {
-                continue;
-            }
+        let mut hits: Vec<(Entity, f32)> = target_query
+            .iter()
+            .filter(|(_, target_stats, target, target_transform, action, _)| {
+                // Avoid friendly fire
+                if target_stats.team == attack.team {
+                    return false;
+                }
+
+                let invulnerable =
+                    action.is_some_and(|action| action.get() == components::Action::Dodge && action.progress() < DODGE_INVULNERABLE);
+
+                !invulnerable && did_hit(attack, origin, target, target_transform)
+            })
+            .map(|(target_entity, _, _, target_transform, _, _)| {
+                (target_entity, attack.origin.distance(target_transform.translation.current))
+            })
+            .collect();
+
+        if attack.line_of_sight && !hits.is_empty() {
+            let walls = walls.get_or_insert_with(|| {
+                wall_query
+                    .iter()
+                    .flat_map(|(c, t)| {
+                        c.polygons
+                            .iter()
+                            .map(move |p| p.transform(t.translation.current, t.rotation.current))
+                    })
+                    .collect()
+            });
+
+            hits.retain(|(target_entity, _)| {
+                target_query.get(*target_entity).is_ok_and(|(_, _, _, target_transform, _, _)| {
+                    let target = target_transform.translation.current;
+                    engine::collision::check_line_of_sight(origin, vec2(target.x, target.z), walls)
+                })
+            });
+        }
+
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some(max_targets) = attack.max_targets {
+            hits.truncate(max_targets);
+        }
 
-            if did_hit(attack_center, attack.radius, target, target_transform) {
-                target_stats.health.changes.push(components::HealthChange::new(
-                    -rng.gen_range(attack.damage.clone()).round(),
-                    components::HealthChangeType::Once,
-                ));
+        let mut attacker_hit_stop = 0;
+
+        for (target_entity, _) in hits {
+            if let Ok((_, mut target_stats, _, target_transform, action, movement)) = target_query.get_mut(target_entity) {
+                let critical = rng.gen::<f32>() < attack.critical_chance;
+                let damage = components::stats::resolve_damage(
+                    rng.gen_range(attack.damage.clone()),
+                    attack.damage_type,
+                    critical,
+                    target_stats.armor,
+                    &target_stats.resistances,
+                );
+
+                target_stats
+                    .health
+                    .changes
+                    .push(components::HealthChange::new(-damage, components::HealthChangeType::Once));
+
+                let (knockback, hit_stop) = if critical {
+                    (
+                        attack.knockback * components::stats::CRITICAL_MULTIPLIER,
+                        attack.hit_stop + CRITICAL_HIT_STOP,
+                    )
+                } else {
+                    (attack.knockback, attack.hit_stop)
+                };
+
+                if let Some(mut movement) = movement {
+                    let away = target_transform.translation.current - attack.origin;
+                    let away = vec3(away.x, 0.0, away.z);
+                    if !away.is_zero() {
+                        // The steps add up to the full knockback distance while it fades out
+                        movement.knockback = away.normalize() * knockback * (1.0 - components::KNOCKBACK_DAMPING);
+                    }
+                }
+
+                // Only entities with actions stay around long enough to be frozen
+                if hit_stop > 0 && action.is_some() {
+                    commands.entity(target_entity).insert(components::HitStop::new(hit_stop));
+                    attacker_hit_stop = attacker_hit_stop.max(hit_stop);
+                }
             }
         }
 
+        // The attacker may have been streamed out since swinging
+        if attacker_hit_stop > 0 && actor_query.contains(attack.attacker) {
+            commands.entity(attack.attacker).insert(components::HitStop::new(attacker_hit_stop));
+        }
+
         commands.entity(entity).despawn();
     }
 }
 
 fn did_hit(
-    attack: Vector2<f32>,
-    radius: f32,
+    attack: &components::Attack,
+    origin: Vector2<f32>,
     target: &components::Collision,
     target_transform: &engine::ecs::components::Transform,
 ) -> bool {
-    let c = target
-        .polygons
-        .iter()
-        .flat_map(move |p| p.transform(target_transform.translation.current, target_transform.rotation.current))
-        .collect();
-
-    engine::collision::check_collision_circle(&c, attack, radius)
+    target.polygons.iter().any(|p| {
+        let polygon = p.transform(target_transform.translation.current, target_transform.rotation.current);
+        engine::collision::check_collision_shape(&polygon, attack.shape, origin, attack.direction)
+    })
 }
@@ -0,0 +1,72 @@
+use crate::{map::LootDefinition, world::*};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::DespawnRecursiveExt;
+use rand::Rng;
+
+const LOOT_SCATTER: f32 = 0.6;
+const HEALTH_LOOT_COLOR: Vector3<f32> = Vector3::new(1.0, 0.2, 0.2);
+const EXPERIENCE_LOOT_COLOR: Vector3<f32> = Vector3::new(0.4, 0.6, 1.0);
+
+pub fn destructible(
+    mut commands: Commands,
+    mut tile_query: Query<&mut components::Tile>,
+    query: Query<(
+        Entity,
+        &components::Destructible,
+        &components::Stats,
+        &engine::ecs::components::Transform,
+    )>,
+) {
+    for (entity, destructible, stats, transform) in query.iter() {
+        if stats.health.get() > 0.0 {
+            continue;
+        }
+
+        // Remembered on the tile, so it is spawned broken the next time around
+        if let Ok(mut tile) = tile_query.get_mut(destructible.tile) {
+            if let Some(decor) = tile.decor.get_mut(destructible.decor) {
+                decor.destroyed = true;
+            }
+        }
+
+        match &destructible.broken {
+            Some(broken) => {
+                commands
+                    .entity(entity)
+                    .insert(broken.clone())
+                    .remove::<(components::Destructible, components::Stats, components::Collision)>();
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+
+        spawn_loot(&mut commands, &destructible.loot, transform.translation.current);
+    }
+}
+
+/// Rolls every entry of the loot table and scatters the pickups around `position`.
+pub fn spawn_loot(commands: &mut Commands, loot: &[LootDefinition], position: Vector3<f32>) {
+    let mut rng = rand::thread_rng();
+
+    for loot in loot.iter() {
+        if rng.gen::<f32>() >= loot.chance {
+            continue;
+        }
+
+        let offset = vec3(rng.gen::<f32>() - 0.5, 0.0, rng.gen::<f32>() - 0.5) * LOOT_SCATTER * 2.0;
+        let color = if loot.reward.health > 0.0 {
+            HEALTH_LOOT_COLOR
+        } else {
+            EXPERIENCE_LOOT_COLOR
+        };
+
+        commands.spawn((
+            components::Loot {
+                reward: loot.reward.clone(),
+            },
+            engine::ecs::components::Light::new(color, 1.0, 1.5, vec3(0.0, 0.5, 0.0), 1.0),
+            engine::ecs::components::Transform::from_translation(position + offset),
+            engine::ecs::components::Render { cull_frustum: true },
+            components::Flicker::new(0.2, 0.05),
+        ));
+    }
+}
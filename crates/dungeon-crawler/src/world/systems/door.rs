@@ -0,0 +1,84 @@
+use crate::world::*;
+use bevy_ecs::prelude::*;
+
+const DOOR_OPEN_SECONDS: f32 = 0.8;
+const DOOR_SINK: f32 = 2.5;
+
+pub fn door(
+    mut commands: Commands,
+    mut interactions: EventReader<events::Interaction>,
+    time: Res<engine::ecs::resources::Time>,
+    inventory_query: Query<&components::Inventory>,
+    mut door_query: Query<(Entity, &mut components::Door, &mut engine::ecs::components::Transform)>,
+) {
+    for interaction in interactions.iter() {
+        match &interaction.action {
+            components::InteractAction::Door => {
+                if let Ok((entity, mut door, _)) = door_query.get_mut(interaction.target) {
+                    let unlocked = match door.lock {
+                        Some(lock) => inventory_query
+                            .get(interaction.actor)
+                            .is_ok_and(|inventory| inventory.keys.contains(&lock)),
+                        None => true,
+                    };
+
+                    if unlocked && door.is_closed() {
+                        door.state = components::DoorState::Opening(0.0);
+                        commands
+                            .entity(entity)
+                            .remove::<(components::Collision, components::Interactable)>();
+                    }
+                }
+            }
+            components::InteractAction::Lever(position) => {
+                let lever_door = door_query
+                    .iter_mut()
+                    .find(|(_, door, _)| door.closed_position.distance(*position) < 0.01);
+
+                if let Some((entity, mut door, _)) = lever_door {
+                    door.state = match door.state {
+                        components::DoorState::Closed => components::DoorState::Opening(0.0),
+                        components::DoorState::Opening(progress) => components::DoorState::Closing(progress),
+                        components::DoorState::Open => components::DoorState::Closing(1.0),
+                        components::DoorState::Closing(progress) => components::DoorState::Opening(progress),
+                    };
+                    commands.entity(entity).remove::<components::Collision>();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let step = 1.0 / (DOOR_OPEN_SECONDS * config::UPDATES_PER_SECOND);
+    for (entity, mut door, mut transform) in door_query.iter_mut() {
+        let progress = match door.state {
+            components::DoorState::Opening(progress) => {
+                let progress = (progress + step).min(1.0);
+                door.state = if progress < 1.0 {
+                    components::DoorState::Opening(progress)
+                } else {
+                    components::DoorState::Open
+                };
+                progress
+            }
+            components::DoorState::Closing(progress) => {
+                let progress = (progress - step).max(0.0);
+                door.state = if progress > 0.0 {
+                    components::DoorState::Closing(progress)
+                } else {
+                    // Solid again only once it is all the way up
+                    if !door.collisions.is_empty() {
+                        commands.entity(entity).insert(components::Collision::new(door.collisions.clone()));
+                    }
+                    components::DoorState::Closed
+                };
+                progress
+            }
+            _ => continue,
+        };
+
+        transform
+            .translation
+            .set(door.closed_position - Vector3::unit_y() * DOOR_SINK * progress, time.frame);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    config,
+    map::HazardKind,
+    world::{components, events},
+};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Children;
+use cgmath::*;
+use engine::ecs::components::{Light, Particle, Sound, SoundEffects, Transform};
+
+const SPIKES_DEPTH: f32 = 0.5;
+const WARNING_SHAKE: f32 = 20.0;
+/// Fire and poison burn in ticks, damage every update would keep the target flinching.
+const BURN_TICK: f32 = 0.5;
+
+pub fn hazard(
+    mut commands: Commands,
+    mut interactions: EventReader<events::Interaction>,
+    time: Res<engine::ecs::resources::Time>,
+    mut hazard_query: Query<(
+        Entity,
+        &mut components::Hazard,
+        &mut Transform,
+        &mut Light,
+        &mut SoundEffects,
+        Option<&Children>,
+    )>,
+    mut particle_query: Query<&mut Particle>,
+    mut target_query: Query<(Entity, &mut components::Stats, &Transform, &components::Collision), Without<components::Hazard>>,
+) {
+    for interaction in interactions.iter() {
+        if let components::InteractAction::Disarm = interaction.action {
+            if let Ok((_, mut hazard, _, _, _, _)) = hazard_query.get_mut(interaction.target) {
+                hazard.disarmed = true;
+            }
+
+            commands.entity(interaction.target).remove::<components::Interactable>();
+        }
+    }
+
+    for (entity, mut hazard, mut transform, mut light, mut sound_effects, children) in hazard_query.iter_mut() {
+        let previous = hazard.phase();
+        let previous_tick = (hazard.timer / BURN_TICK) as u32;
+        hazard.timer += 1.0 / config::UPDATES_PER_SECOND;
+        let phase = hazard.phase();
+
+        if previous == components::HazardPhase::Idle && phase != components::HazardPhase::Idle {
+            if let Some(sound) = &hazard.sound {
+                sound_effects.set(format!("{:?}_hazard", entity), Sound::new(sound));
+            }
+        }
+
+        // Telegraph: the light pulses and the emitters smoulder before the hazard goes off
+        let strength = match phase {
+            components::HazardPhase::Idle => 0.0,
+            components::HazardPhase::Warning(progress) => progress * (0.6 + 0.4 * (hazard.timer * WARNING_SHAKE).sin()) * 0.5,
+            components::HazardPhase::Active => 1.0,
+        };
+
+        let base_intensity = light.base_intensity;
+        light.intensity.set(base_intensity * strength, time.frame);
+
+        for child in children.iter().flat_map(|children| children.iter()) {
+            if let Ok(mut particle) = particle_query.get_mut(*child) {
+                let base_strength = particle.base_strength;
+                particle.strength.set(base_strength * strength, time.frame);
+            }
+        }
+
+        if hazard.kind == HazardKind::Spikes {
+            let height = match phase {
+                components::HazardPhase::Idle => -SPIKES_DEPTH,
+                components::HazardPhase::Warning(progress) => -SPIKES_DEPTH * (1.0 - progress * 0.3),
+                components::HazardPhase::Active => 0.0,
+            };
+            let position = transform.translation.current;
+            transform.translation.set(vec3(position.x, height, position.z), time.frame);
+        }
+
+        let new_tick = hazard.kind != HazardKind::Spikes && (hazard.timer / BURN_TICK) as u32 != previous_tick;
+        if phase != components::HazardPhase::Active || new_tick {
+            hazard.hit.clear();
+        }
+
+        if phase != components::HazardPhase::Active {
+            continue;
+        }
+
+        let position = transform.translation.current;
+        for (target, mut stats, target_transform, collision) in target_query.iter_mut() {
+            let offset = target_transform.translation.current - position;
+            let inside = vec2(offset.x, offset.z).magnitude() < hazard.radius + collision.radius;
+
+            if !inside || stats.health.get() <= 0.0 || hazard.hit.contains(&target) {
+                continue;
+            }
+
+            hazard.hit.push(target);
+
+            let damage = match hazard.kind {
+                HazardKind::Spikes => hazard.damage,
+                HazardKind::Fire | HazardKind::Poison => hazard.damage * BURN_TICK,
+            };
+
+            let damage = components::stats::resolve_damage(damage, hazard.damage_type(), false, stats.armor, &stats.resistances);
+            stats
+                .health
+                .changes
+                .push(components::HealthChange::new(-damage, components::HealthChangeType::Once));
+        }
+    }
+}
@@ -5,13 +5,20 @@ use bevy_hierarchy::*;
 pub fn health(
     mut commands: Commands,
     mut query: ParamSet<(
-        Query<(Entity, &mut components::Stats, Option<&mut components::ActionExecutor>)>,
+        Query<(
+            Entity,
+            &mut components::Stats,
+            Option<&mut components::ActionExecutor>,
+            Option<&components::Boss>,
+            Option<&components::Destructible>,
+        )>,
         Query<&mut components::Stats, With<components::UserControl>>,
     )>,
 ) {
     let mut total_experience = vec![];
+    let mut rewards = vec![];
 
-    for (entity, mut stats, mut action) in query.p0().iter_mut() {
+    for (entity, mut stats, mut action, boss, destructible) in query.p0().iter_mut() {
         let previous = stats.health.get();
         if previous >= 0.0 {
             stats.health.changes = stats
@@ -53,13 +60,21 @@ pub fn health(
                     action.set_action(components::Action::Death, 100.0, 0.0);
                     total_experience.push((stats.get_kill_experience(), stats.get_level()));
 
-                    commands
-                        .entity(entity)
-                        .remove::<(components::Agressor, components::Target, components::Collision)>();
+                    if let Some(boss) = boss {
+                        rewards.push(boss.reward.clone());
+                    }
+
+                    commands.entity(entity).remove::<(
+                        components::Agressor,
+                        components::Behaviour,
+                        components::Boss,
+                        components::Target,
+                        components::Collision,
+                    )>();
                 } else {
                     action.set_action(components::Action::Hit, stats.get_recovery_time(), 0.0);
                 }
-            } else if stats.health.get() <= 0.0 {
+            } else if stats.health.get() <= 0.0 && destructible.is_none() {
                 commands.entity(entity).despawn_recursive();
             }
         }
@@ -72,10 +87,19 @@ pub fn health(
             stats.experience += (*exp as f32 + (*exp as f32 * (*kill_level as f32 / level as f32)).powf(1.2)) as u32;
         }
 
+        for reward in rewards.iter() {
+            stats.experience += reward.experience;
+            stats
+                .health
+                .changes
+                .push(components::HealthChange::new(reward.health, components::HealthChangeType::Once));
+        }
+
         if stats.get_level() > level {
             stats.level_up();
         }
 
         total_experience.clear();
+        rewards.clear();
     }
 }
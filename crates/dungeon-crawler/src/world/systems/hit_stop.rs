@@ -0,0 +1,27 @@
+use crate::{config, world::components};
+use bevy_ecs::prelude::*;
+
+pub fn hit_stop(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut components::HitStop,
+        &mut engine::ecs::components::Animations,
+        Option<&mut components::ActionExecutor>,
+    )>,
+) {
+    for (entity, mut hit_stop, mut animations, action) in query.iter_mut() {
+        if hit_stop.steps == 0 {
+            animations.frozen = false;
+            commands.entity(entity).remove::<components::HitStop>();
+            continue;
+        }
+
+        hit_stop.steps -= 1;
+        animations.frozen = true;
+
+        if let Some(mut action) = action {
+            action.delay(config::time_step());
+        }
+    }
+}
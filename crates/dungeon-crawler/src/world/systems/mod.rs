@@ -1,19 +1,45 @@
 mod actions;
 mod aggression;
+mod alert;
+mod behaviour;
+mod boss;
+mod buff;
+mod chest;
 mod collision;
 mod damage;
+mod destructible;
+mod door;
 mod flicker;
+mod hazard;
 mod health;
+mod hit_stop;
 mod movement;
+mod pickup;
+mod shrine;
+mod stamina;
+mod steering;
 mod tile;
 mod user_control;
 
 pub use actions::actions;
 pub use aggression::aggression;
+pub use alert::alert;
+pub use behaviour::behaviour;
+pub use boss::boss;
+pub use buff::buff;
+pub use chest::chest;
 pub use collision::collision;
 pub use damage::damage;
+pub use destructible::destructible;
+pub use door::door;
 pub use flicker::flicker;
+pub use hazard::hazard;
 pub use health::health;
+pub use hit_stop::hit_stop;
 pub use movement::movement;
-pub use tile::tile;
+pub use pickup::pickup;
+pub use shrine::shrine;
+pub use stamina::stamina;
+pub use steering::steering;
+pub use tile::{spawn_hostile, tile};
 pub use user_control::user_control;
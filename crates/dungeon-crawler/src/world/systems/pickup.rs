@@ -0,0 +1,37 @@
+use crate::world::*;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::DespawnRecursiveExt;
+
+const PICKUP_DISTANCE: f32 = 0.8;
+
+pub fn pickup(
+    mut commands: Commands,
+    mut player_query: Query<
+        (
+            &engine::ecs::components::Transform,
+            &mut components::Inventory,
+            &mut components::Stats,
+        ),
+        With<components::UserControl>,
+    >,
+    key_query: Query<(Entity, &components::Key, &engine::ecs::components::Transform)>,
+    loot_query: Query<(Entity, &components::Loot, &engine::ecs::components::Transform)>,
+) {
+    for (transform, mut inventory, mut stats) in player_query.iter_mut() {
+        let position = transform.translation.current;
+
+        for (entity, key, key_transform) in key_query.iter() {
+            if key_transform.translation.current.distance(position) < PICKUP_DISTANCE {
+                inventory.keys.push(key.lock);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+
+        for (entity, loot, loot_transform) in loot_query.iter() {
+            if loot_transform.translation.current.distance(position) < PICKUP_DISTANCE {
+                stats.add_reward(&loot.reward);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    config,
+    map::Role,
+    world::{components, events},
+};
+use bevy_ecs::prelude::*;
+
+const SHRINE_HEALING: f32 = 5.0;
+
+pub fn shrine(
+    mut commands: Commands,
+    mut interactions: EventReader<events::Interaction>,
+    tile_query: Query<&components::Tile>,
+    mut query: Query<
+        (
+            Entity,
+            &mut components::Stats,
+            &engine::ecs::components::Transform,
+            Option<&components::Buff>,
+        ),
+        With<components::UserControl>,
+    >,
+) {
+    for interaction in interactions.iter() {
+        let buff = match &interaction.action {
+            components::InteractAction::Shrine(buff) => buff,
+            _ => continue,
+        };
+
+        if let Ok((entity, mut stats, _, active)) = query.get_mut(interaction.actor) {
+            // A new blessing replaces the one that is still running
+            if let Some(active) = active {
+                stats.strength -= active.strength;
+                stats.dexterity -= active.dexterity;
+            }
+
+            stats.strength += buff.strength;
+            stats.dexterity += buff.dexterity;
+            commands.entity(entity).insert(components::Buff {
+                strength: buff.strength,
+                dexterity: buff.dexterity,
+                remaining: buff.seconds,
+            });
+        }
+
+        commands.entity(interaction.target).remove::<components::Interactable>();
+    }
+
+    for (_, mut stats, transform, _) in query.iter_mut() {
+        let position = transform.translation.current;
+        let in_shrine = tile_query.iter().any(|tile| tile.role == Role::Shrine && tile.contains(position));
+
+        if in_shrine && stats.health.get() > 0.0 && stats.health.get() < stats.get_base_health() {
+            stats.health.changes.push(components::HealthChange::new(
+                SHRINE_HEALING / config::UPDATES_PER_SECOND,
+                components::HealthChangeType::Once,
+            ));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{config, world::components};
+use bevy_ecs::prelude::*;
+
+const STAMINA_REGENERATION: f32 = 15.0;
+const RUN_STAMINA: f32 = 10.0;
+
+pub fn stamina(
+    mut query: Query<(&mut components::Stats, &components::Movement, &components::ActionExecutor), With<components::UserControl>>,
+) {
+    for (mut stats, movement, action) in query.iter_mut() {
+        // The roll is fast as well, but has already paid for itself
+        if action.get() == components::Action::Dodge {
+            continue;
+        }
+
+        if movement.velocity > components::RUN_VELOCITY {
+            stats.use_stamina(RUN_STAMINA / config::UPDATES_PER_SECOND);
+        } else {
+            let stamina = stats.stamina + STAMINA_REGENERATION / config::UPDATES_PER_SECOND;
+            stats.stamina = stamina.min(stats.get_base_stamina());
+        }
+    }
+}
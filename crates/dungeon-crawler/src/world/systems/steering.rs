@@ -0,0 +1,175 @@
+use crate::world::{components::BehaviourState, *};
+use bevy_ecs::prelude::*;
+
+const NEIGHBOUR_DISTANCE: f32 = 2.0;
+const SEPARATION_DISTANCE: f32 = 0.4;
+const SEPARATION_SPEED: f32 = 0.01;
+const AVOIDANCE_STEPS: f32 = 25.0;
+const SLOT_SPACING: f32 = 2.2;
+const MAX_SLOTS: usize = 8;
+
+struct Neighbour {
+    entity: Entity,
+    position: Vector3<f32>,
+    velocity: Vector3<f32>,
+    radius: f32,
+}
+
+pub fn steering(
+    mut query: Query<(
+        Entity,
+        &mut components::Movement,
+        &components::Collision,
+        &engine::ecs::components::Transform,
+        Option<&mut components::Behaviour>,
+        Option<&components::Agressor>,
+        Option<&components::Weapon>,
+    )>,
+) {
+    let neighbours: Vec<Neighbour> = query
+        .iter()
+        .map(|(entity, movement, collision, transform, ..)| Neighbour {
+            entity,
+            position: transform.translation.current,
+            velocity: movement.to,
+            radius: collision.radius,
+        })
+        .collect();
+
+    assign_attack_slots(&mut query);
+
+    for (entity, mut movement, collision, transform, behaviour, ..) in query.iter_mut() {
+        if behaviour.is_none() {
+            continue;
+        }
+
+        let position = transform.translation.current;
+        let velocity = movement.to;
+        let speed = velocity.magnitude();
+        let mut separation = Vector3::zero();
+        let mut avoidance = Vector3::zero();
+
+        for neighbour in neighbours.iter().filter(|n| n.entity != entity) {
+            let offset = position - neighbour.position;
+            let distance = offset.magnitude();
+            let combined_radius = collision.radius + neighbour.radius;
+            if distance > combined_radius + NEIGHBOUR_DISTANCE || distance == 0.0 {
+                continue;
+            }
+
+            // Push away from anyone standing closer than the separation distance
+            let gap = distance - combined_radius;
+            if gap < SEPARATION_DISTANCE {
+                separation += offset / distance * (1.0 - gap.max(0.0) / SEPARATION_DISTANCE);
+            }
+
+            // Velocity obstacle: steer sideways when the paths meet within the look-ahead window
+            let relative_velocity = velocity - neighbour.velocity;
+            let relative_speed = relative_velocity.magnitude2();
+            if relative_speed == 0.0 {
+                continue;
+            }
+
+            let time = (-offset.dot(relative_velocity) / relative_speed).clamp(0.0, AVOIDANCE_STEPS);
+            let closest = offset + relative_velocity * time;
+            let closest_distance = closest.magnitude();
+            if time > 0.0 && closest_distance < combined_radius {
+                let away = if closest_distance > 0.0 {
+                    closest / closest_distance
+                } else {
+                    vec3(-relative_velocity.z, 0.0, relative_velocity.x).normalize()
+                };
+
+                avoidance += away * (1.0 - time / AVOIDANCE_STEPS);
+            }
+        }
+
+        if speed > 0.0 {
+            let steered = velocity / speed + separation + avoidance;
+            if steered.magnitude2() > 0.0 {
+                movement.to = steered.normalize() * speed;
+            }
+        } else if separation.magnitude2() > 0.0 {
+            movement.to = separation.normalize() * SEPARATION_SPEED;
+        }
+    }
+}
+
+/// Spreads hostiles chasing the same target over rings of slots around it,
+/// nearest hostile picks first and the overflow waits on the outer rings.
+fn assign_attack_slots(
+    query: &mut Query<(
+        Entity,
+        &mut components::Movement,
+        &components::Collision,
+        &engine::ecs::components::Transform,
+        Option<&mut components::Behaviour>,
+        Option<&components::Agressor>,
+        Option<&components::Weapon>,
+    )>,
+) {
+    let mut groups: Vec<(Vector3<f32>, Vec<(Entity, Vector3<f32>)>)> = vec![];
+
+    for (entity, _, _, transform, behaviour, agressor, _) in query.iter_mut() {
+        let mut behaviour = match behaviour {
+            Some(behaviour) => behaviour,
+            None => continue,
+        };
+        behaviour.slot = None;
+
+        let target = match (behaviour.state, agressor.and_then(|a| a.visible_target)) {
+            (BehaviourState::Chase | BehaviourState::Attack, Some(target)) => target,
+            _ => continue,
+        };
+
+        let position = transform.translation.current;
+        match groups.iter_mut().find(|(t, _)| t.distance(target) < 0.01) {
+            Some((_, members)) => members.push((entity, position)),
+            None => groups.push((target, vec![(entity, position)])),
+        }
+    }
+
+    for (target, mut members) in groups {
+        members.sort_by(|(_, a), (_, b)| a.distance(target).total_cmp(&b.distance(target)));
+
+        let mut taken: Vec<(usize, usize)> = vec![];
+        for (entity, position) in members {
+            let (_, _, collision, _, behaviour, agressor, weapon) = match query.get_mut(entity) {
+                Ok(result) => result,
+                Err(_) => continue,
+            };
+            let target_radius = agressor.map_or(0.0, |a| a.target_radius);
+            let ring = collision.radius + target_radius + weapon.map_or(0.0, |w| w.distance);
+            let slots = ((std::f32::consts::TAU * ring / (collision.radius * SLOT_SPACING)) as usize).clamp(1, MAX_SLOTS);
+
+            let mut rank = 0;
+            let slot = loop {
+                let free: Vec<usize> = (0..slots).filter(|slot| !taken.contains(&(rank, *slot))).collect();
+                if free.is_empty() {
+                    rank += 1;
+                    continue;
+                }
+
+                let nearest = free
+                    .into_iter()
+                    .map(|slot| (slot, slot_position(target, ring, rank, slot, slots, collision.radius)))
+                    .min_by(|(_, a), (_, b)| a.distance(position).total_cmp(&b.distance(position)))
+                    .unwrap();
+
+                taken.push((rank, nearest.0));
+                break nearest.1;
+            };
+
+            if let Some(mut behaviour) = behaviour {
+                behaviour.slot = Some(slot);
+            }
+        }
+    }
+}
+
+fn slot_position(target: Vector3<f32>, ring: f32, rank: usize, slot: usize, slots: usize, radius: f32) -> Vector3<f32> {
+    let angle = std::f32::consts::TAU * (slot as f32 + rank as f32 * 0.5) / slots as f32;
+    let distance = ring + rank as f32 * radius * SLOT_SPACING;
+
+    target + vec3(angle.sin(), 0.0, angle.cos()) * distance
+}
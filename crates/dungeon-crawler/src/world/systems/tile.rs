@@ -4,15 +4,21 @@ use rand::{prelude::StdRng, Rng, SeedableRng};
 
 use crate::world::*;
 
+const KEY_LIGHT_COLOR: Vector3<f32> = Vector3::new(1.0, 0.8, 0.3);
+const DOOR_RADIUS: f32 = 1.5;
+const DISARM_REACH: f32 = 0.8;
+
 pub fn tile(mut commands: Commands, camera: Res<engine::ecs::resources::Camera>, mut query: Query<(Entity, &mut components::Tile)>) {
     let mut rng = StdRng::seed_from_u64(54); // TODO: seed
     let flicker_speed = rng.gen::<f32>() * 0.05 + 0.02;
 
     for (entity, mut tile) in query.iter_mut() {
         match tile.state {
-            components::TileState::Active => {
+            components::TileState::Active(tile_id) => {
+                // Only what is spawned from the tile goes, the tile keeps its state for the next time around
                 if !camera.frustum.test_bounding_box(&tile.bounding_box) {
-                    commands.entity(entity).despawn_recursive();
+                    commands.entity(tile_id).despawn_recursive();
+                    tile.state = components::TileState::Destroyed;
                 }
             }
 
@@ -29,18 +35,36 @@ pub fn tile(mut commands: Commands, camera: Res<engine::ecs::resources::Camera>,
                         tile_entity.insert(components::Collision::new(tile.collisions.clone()));
                     }
 
-                    for decor in tile.decor.iter() {
+                    for (index, decor) in tile.decor.iter().enumerate() {
+                        let model = match (decor.destroyed, &decor.broken_model) {
+                            (false, _) => decor.model.clone(),
+                            (true, Some(broken_model)) => broken_model.clone(),
+                            (true, None) => continue,
+                        };
+
                         let mut decor_entity = commands.spawn((
-                            decor.model.clone(),
+                            model,
                             engine::ecs::components::Transform::from_translation_angle(decor.position, decor.rotation),
                             engine::ecs::components::Render { cull_frustum: true },
                             engine::ecs::components::Shadow,
                         ));
 
-                        if !decor.collisions.is_empty() {
+                        if !decor.collisions.is_empty() && !decor.destroyed {
                             decor_entity.insert(components::Collision::new(decor.collisions.clone()));
                         }
 
+                        if let (Some(destructible), false) = (&decor.destructible, decor.destroyed) {
+                            decor_entity.insert((
+                                components::Stats::new(0, destructible.vitality, 0, 0, config::TEAM_NEUTRAL),
+                                components::Destructible {
+                                    tile: entity,
+                                    decor: index,
+                                    broken: decor.broken_model.clone(),
+                                    loot: destructible.loot.clone(),
+                                },
+                            ));
+                        }
+
                         let decor_id = decor_entity.id();
                         commands.entity(tile_id).push_children(&[decor_id]);
 
@@ -77,36 +101,216 @@ pub fn tile(mut commands: Commands, camera: Res<engine::ecs::resources::Camera>,
                         }
                     }
 
+                    tile.state = components::TileState::Active(tile_id);
+                    if tile.populated {
+                        continue;
+                    }
+
                     for hostile in tile.hostiles.iter() {
-                        commands.spawn((
-                            components::Name::new("Skeleton Warrior"),
-                            hostile.model.clone(),
-                            components::Collision::new(hostile.collider.clone()),
-                            engine::ecs::components::Animations::new("base", "idle", engine::ecs::components::AnimationStatus::Repeat),
-                            engine::ecs::components::Transform::from_translation_scale(hostile.position, 0.8),
+                        spawn_hostile(&mut commands, hostile);
+                    }
+
+                    for door in tile.doors.iter() {
+                        let mut door_entity = commands.spawn((
+                            door.model.clone(),
+                            components::Door::new(door.lock, door.position, door.collisions.clone()),
+                            engine::ecs::components::Transform::from_translation_angle(door.position, door.rotation),
                             engine::ecs::components::Render { cull_frustum: true },
-                            engine::ecs::components::SoundEffects::default(),
-                            components::Stats::new(10, 8, 10, components::stats::get_level_experience(3), config::TEAM_HOSTILE),
-                            components::Weapon {
-                                damage: 2.0..5.0,
-                                distance: 0.5,
-                                radius: 0.25,
-                                time: 1.0,
-                            },
-                            components::Agressor::new(6.0),
-                            components::Movement::new(10.0),
                             engine::ecs::components::Shadow,
-                            components::ActionExecutor::new(),
                         ));
+
+                        if !door.collisions.is_empty() {
+                            door_entity.insert(components::Collision::new(door.collisions.clone()));
+                        }
+
+                        if !door.lever {
+                            let prompt = if door.lock.is_some() { "Unlock door" } else { "Open door" };
+                            door_entity.insert(components::Interactable::new(
+                                DOOR_RADIUS,
+                                components::InteractAction::Door,
+                                prompt,
+                            ));
+                        }
                     }
 
-                    tile.state = components::TileState::Active;
+                    for object in tile.objects.iter() {
+                        let mut object_entity = commands.spawn((
+                            object.model.clone(),
+                            object.interactable.clone(),
+                            engine::ecs::components::Transform::from_translation_angle(object.position, object.rotation),
+                            engine::ecs::components::Render { cull_frustum: true },
+                            engine::ecs::components::Shadow,
+                        ));
+
+                        if !object.collisions.is_empty() {
+                            object_entity.insert(components::Collision::new(object.collisions.clone()));
+                        }
+                    }
+
+                    for hazard in tile.hazards.iter() {
+                        let component = components::Hazard::new(&hazard.definition, hazard.offset);
+                        let radius = component.radius;
+                        let mut hazard_entity = commands.spawn((
+                            engine::ecs::components::Light::new(component.color(), 1.0, radius * 2.0 + 1.0, vec3(0.0, 0.8, 0.0), 1.0),
+                            engine::ecs::components::Transform::from_translation_angle(hazard.position, hazard.rotation),
+                            engine::ecs::components::Render { cull_frustum: true },
+                            engine::ecs::components::SoundEffects::default(),
+                            component,
+                        ));
+
+                        if let Some(model) = &hazard.model {
+                            hazard_entity.insert((model.clone(), engine::ecs::components::Shadow));
+                        }
+
+                        if hazard.definition.disarmable {
+                            hazard_entity.insert(components::Interactable::new(
+                                radius + DISARM_REACH,
+                                components::InteractAction::Disarm,
+                                "Disarm trap",
+                            ));
+                        }
+
+                        let hazard_id = hazard_entity.id();
+                        for e in hazard.emitters.iter() {
+                            let id = e.emitter_id.clone();
+                            let emitter_entity = commands.spawn((
+                                engine::ecs::components::Particle::new(id, e.start_color, e.end_color, e.size, e.strength),
+                                engine::ecs::components::Render { cull_frustum: true },
+                                engine::ecs::components::Transform::from_translation_angle(e.position, e.rotation),
+                            ));
+
+                            let emitter_entity_id = emitter_entity.id();
+                            commands.entity(hazard_id).push_children(&[emitter_entity_id]);
+                        }
+                    }
+
+                    for key in tile.keys.iter() {
+                        let mut key_entity = commands.spawn((
+                            components::Key { lock: key.lock },
+                            engine::ecs::components::Light::new(KEY_LIGHT_COLOR, 1.0, 1.5, vec3(0.0, 0.5, 0.0), 1.0),
+                            engine::ecs::components::Transform::from_translation(key.position),
+                            engine::ecs::components::Render { cull_frustum: true },
+                            components::Flicker::new(0.2, flicker_speed),
+                        ));
+
+                        if let Some(model) = &key.model {
+                            key_entity.insert((model.clone(), engine::ecs::components::Shadow));
+                        }
+                    }
+
+                    tile.populated = true;
                 }
             }
         }
     }
 }
 
+pub fn spawn_hostile(commands: &mut Commands, hostile: &components::Hostile) -> Entity {
+    let definition = &hostile.definition;
+    let behaviour = &definition.behaviour;
+
+    let mut hostile_entity = commands.spawn((
+        components::Name::new(&definition.name),
+        hostile.model.clone(),
+        components::Collision::new(hostile.collider.clone()),
+        engine::ecs::components::Animations::from_graph(hostile.animation_graph.clone()),
+        engine::ecs::components::Transform::from_translation_scale(hostile.position, definition.scale),
+        engine::ecs::components::Render { cull_frustum: true },
+        engine::ecs::components::SoundEffects::default(),
+        components::Stats::new(
+            definition.strength,
+            definition.vitality,
+            definition.dexterity,
+            components::stats::get_level_experience(definition.level),
+            config::TEAM_HOSTILE,
+        )
+        .with_defense(definition.armor, definition.resistances.clone()),
+        components::Weapon::new(&definition.weapon),
+        components::Agressor::new(behaviour),
+        components::Behaviour::new(hostile.position, hostile.route.clone(), behaviour),
+        components::Movement::new(10.0),
+        engine::ecs::components::Shadow,
+        components::ActionExecutor::new(),
+    ));
+
+    if let Some(boss) = &hostile.boss {
+        hostile_entity.insert(boss.clone());
+    }
+
+    hostile_entity.id()
+}
+
 fn get_flicker(flicker: Option<f32>, speed: f32) -> Option<components::Flicker> {
     flicker.map(|flicker| components::Flicker::new(flicker, speed))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::DestructibleDefinition;
+    use engine::ecs::components::Model;
+
+    fn barrel() -> components::Decor {
+        components::Decor {
+            model: Model::new("barrel"),
+            lights: vec![],
+            emitters: vec![],
+            rotation: 0.0,
+            position: vec3(1.0, 0.0, 1.0),
+            collisions: vec![],
+            destructible: Some(DestructibleDefinition {
+                name: "barrel".into(),
+                vitality: 1,
+                broken: None,
+                loot: vec![],
+            }),
+            broken_model: None,
+            destroyed: false,
+        }
+    }
+
+    fn stream(world: &mut World, target: Vector3<f32>) {
+        world.resource_mut::<engine::ecs::resources::Camera>().set(target);
+        SystemStage::single(tile).run(world);
+    }
+
+    fn models(world: &mut World) -> Vec<String> {
+        world.query::<&Model>().iter(world).map(|model| model.key.clone()).collect()
+    }
+
+    #[test]
+    fn broken_decor_stays_broken_when_streamed_back_in() {
+        let mut world = World::new();
+        world.insert_resource(engine::ecs::resources::Camera::new(1.0));
+        let tile_entity = world
+            .spawn(components::Tile::new(
+                Model::new("tile"),
+                vec![],
+                Vector3::zero(),
+                config::TILE_SIZE,
+                0.0,
+                vec![barrel()],
+                vec![],
+            ))
+            .id();
+
+        stream(&mut world, Vector3::zero());
+        assert_eq!(models(&mut world).len(), 2);
+
+        let (barrel, _) = world.query::<(Entity, &components::Destructible)>().single(&world);
+        world.get_mut::<components::Stats>(barrel).unwrap().health.set(0.0);
+        SystemStage::single(systems::destructible).run(&mut world);
+        assert_eq!(models(&mut world), vec!["tile".to_string()]);
+
+        stream(&mut world, vec3(1000.0, 0.0, 1000.0));
+        assert!(models(&mut world).is_empty());
+
+        let tile = world.get::<components::Tile>(tile_entity).expect("Tile was despawned!");
+        assert!(matches!(tile.state, components::TileState::Destroyed));
+        assert!(tile.decor[0].destroyed);
+
+        stream(&mut world, Vector3::zero());
+        assert_eq!(models(&mut world), vec!["tile".to_string()]);
+        assert!(world.query::<&components::Destructible>().iter(&world).next().is_none());
+    }
+}
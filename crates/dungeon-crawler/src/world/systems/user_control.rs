@@ -5,8 +5,18 @@ use engine::ecs::resources::Input;
 use std::time::Duration;
 use winit::event::VirtualKeyCode;
 
+const DODGE_SECONDS: f32 = 0.45;
+const DODGE_COOLDOWN: f32 = 0.9;
+const DODGE_STAMINA: f32 = 20.0;
+const ATTACK_STAMINA: f32 = 8.0;
+/// Last part of an attack during which pressing attack queues the next step of the combo.
+const COMBO_BUFFER: f32 = 0.4;
+/// Distance within which hostiles are shown as the current target.
+const FOCUS_DISTANCE: f32 = 8.0;
+
 struct Target {
     position: Vector3<f32>,
+    radius: f32,
     health: f32,
     max_health: f32,
     name: String,
@@ -15,6 +25,8 @@ struct Target {
 pub fn user_control(
     mut commands: Commands,
     input: Res<Input>,
+    mut interactions: EventWriter<events::Interaction>,
+    interactable_query: Query<(Entity, &components::Interactable, &engine::ecs::components::Transform)>,
     mut query: ParamSet<(
         Query<(
             Entity,
@@ -22,14 +34,16 @@ pub fn user_control(
             &mut components::Movement,
             &mut components::ActionExecutor,
             &mut components::Stats,
-            &components::UserControl,
-            Option<&components::Weapon>,
+            &mut components::UserControl,
+            Option<&mut components::Weapon>,
+            &components::Collision,
         )>,
         Query<(
             &components::Name,
             &components::Agressor,
             &engine::ecs::components::Transform,
             &components::Stats,
+            &components::Collision,
         )>,
     )>,
 ) {
@@ -37,29 +51,57 @@ pub fn user_control(
     let targets: Vec<Target> = query
         .p1()
         .iter()
-        .map(|(n, _, t, s)| Target {
+        .map(|(n, _, t, s, c)| Target {
             position: t.translation.current,
+            radius: c.radius,
             health: s.health.get(),
             max_health: s.get_base_health(),
             name: n.name.clone(),
         })
         .collect();
 
-    for (entity, transform, mut movement, mut action, mut stats, user_control, weapon) in query.p0().iter_mut() {
-        commands.entity(entity).remove::<components::DisplayTarget>();
+    for (entity, transform, mut movement, mut action, mut stats, mut user_control, mut weapon, collision) in query.p0().iter_mut() {
+        commands
+            .entity(entity)
+            .remove::<(components::DisplayTarget, components::DisplayInteraction)>();
 
         movement.target_velocity = 0.0;
 
-        if let Some(joystick) = &input.joystick {
+        // The roll keeps the direction it started in
+        if let (Some(joystick), false) = (&input.joystick, action.get() == components::Action::Dodge) {
             if let Some((direction, strength)) = joystick.get_direction_strength(&input.mouse) {
                 movement.target_velocity = strength * 8.0 / config::UPDATES_PER_SECOND;
                 movement.towards(rot.rotate_vector(vec3(direction.x, 0.0, direction.y)));
             }
         }
 
+        if stats.stamina <= 0.0 {
+            user_control.exhausted = true;
+        } else if stats.stamina >= stats.get_base_stamina() * 0.25 {
+            user_control.exhausted = false;
+        }
+
+        if user_control.exhausted {
+            movement.target_velocity = movement.target_velocity.min(components::RUN_VELOCITY);
+        }
+
+        user_control.dodge_cooldown = (user_control.dodge_cooldown - 1.0 / config::UPDATES_PER_SECOND).max(0.0);
+        let dodging = input.is_pressed(VirtualKeyCode::LShift) || user_control.ui_actions.contains_key(&UiActionCode::Dodge);
+        if dodging
+            && !user_control.dodging
+            && user_control.dodge_cooldown <= 0.0
+            && stats.stamina >= DODGE_STAMINA
+            && action.get() == components::Action::None
+        {
+            stats.use_stamina(DODGE_STAMINA);
+            action.set_action(components::Action::Dodge, DODGE_SECONDS, 0.0);
+            user_control.dodge_cooldown = DODGE_COOLDOWN;
+        }
+        user_control.dodging = dodging;
+
         let focus_target: Option<&Target> = targets
             .iter()
-            .filter(|target| target.position.distance(transform.translation.current) < 8.0)
+            .filter(|target| target.position.distance(transform.translation.current) < FOCUS_DISTANCE)
             .min_by(|a, b| nearest(&a.position, &b.position, transform.translation.current, movement.direction));
 
         if let Some(target) = focus_target {
@@ -82,15 +124,62 @@ pub fn user_control(
             None
         };
 
-        if input.is_pressed(VirtualKeyCode::Space) || user_control.ui_actions.contains_key(&UiActionCode::Attack) {
-            if let Some(target) = focus_target {
-                let direction = target.position - transform.translation.current;
+        let position = transform.translation.current;
+        let interaction = interactable_query
+            .iter()
+            .filter(|(_, interactable, t)| {
+                let direction = t.translation.current - position;
                 let direction = direction.x.atan2(direction.z);
-                movement.direction = direction;
-            };
 
-            if let Some(weapon) = weapon {
-                action.set_action(components::Action::Attack, weapon.time * stats.get_attack_time(), 0.25);
+                t.translation.current.distance(position) < interactable.radius + collision.radius
+                    && (direction - movement.direction).abs() < 1.57
+            })
+            .min_by(|(_, _, a), (_, _, b)| nearest(&a.translation.current, &b.translation.current, position, movement.direction));
+
+        let interacting = input.is_pressed(VirtualKeyCode::E) || user_control.ui_actions.contains_key(&UiActionCode::Interact);
+        if let Some((target, interactable, _)) = interaction {
+            commands.entity(entity).insert(components::DisplayInteraction {
+                prompt: interactable.prompt.clone(),
+            });
+
+            if interacting && !user_control.interacting {
+                interactions.send(events::Interaction {
+                    actor: entity,
+                    target,
+                    action: interactable.action.clone(),
+                });
+            }
+        }
+        user_control.interacting = interacting;
+
+        let attacking = input.is_pressed(VirtualKeyCode::Space) || user_control.ui_actions.contains_key(&UiActionCode::Attack);
+        match action.get() {
+            components::Action::Attack if attacking && action.progress() >= 1.0 - COMBO_BUFFER => user_control.attack_buffered = true,
+            components::Action::Hit | components::Action::Death | components::Action::Dodge => user_control.attack_buffered = false,
+            _ => {}
+        }
+
+        if (attacking || user_control.attack_buffered) && action.get() == components::Action::None {
+            user_control.attack_buffered = false;
+
+            if let Some(weapon) = &mut weapon {
+                let attack_target = targets
+                    .iter()
+                    .filter(|target| {
+                        target.position.distance(transform.translation.current) <= weapon.reach(collision.radius, target.radius)
+                    })
+                    .min_by(|a, b| nearest(&a.position, &b.position, transform.translation.current, movement.direction));
+
+                if let Some(target) = attack_target {
+                    let direction = target.position - transform.translation.current;
+                    let direction = direction.x.atan2(direction.z);
+                    movement.direction = direction;
+                };
+
+                weapon.advance_combo(action.since_finished(components::Action::Attack));
+                let activation = weapon.combo_step().activation;
+                action.set_action(components::Action::Attack, weapon.time * stats.get_attack_time(), activation);
+                stats.use_stamina(ATTACK_STAMINA);
             }
         }
 
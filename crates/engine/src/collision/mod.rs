@@ -1,6 +1,8 @@
 pub use self::polygon::*;
+pub use self::shape::*;
 use cgmath::*;
 mod polygon;
+mod shape;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Intersection {
@@ -101,10 +103,33 @@ fn line_circle(a: Vector2<f32>, b: Vector2<f32>, center: Vector2<f32>, radius: f
     distance <= radius
 }
 
+fn line_line(a1: Vector2<f32>, a2: Vector2<f32>, b1: Vector2<f32>, b2: Vector2<f32>) -> bool {
+    let denominator = (b2.y - b1.y) * (a2.x - a1.x) - (b2.x - b1.x) * (a2.y - a1.y);
+    if denominator == 0.0 {
+        return false;
+    }
+
+    let ua = ((b2.x - b1.x) * (a1.y - b1.y) - (b2.y - b1.y) * (a1.x - b1.x)) / denominator;
+    let ub = ((a2.x - a1.x) * (a1.y - b1.y) - (a2.y - a1.y) * (a1.x - b1.x)) / denominator;
+
+    (0.0..=1.0).contains(&ua) && (0.0..=1.0).contains(&ub)
+}
+
 pub fn check_collision_circle(a: &Polygon, center: Vector2<f32>, radius: f32) -> bool {
     (0..a.len()).any(|i| line_circle(a[i], a[(i + 1) % a.len()], center, radius))
 }
 
+pub fn check_collision_line(a: &Polygon, start: Vector2<f32>, end: Vector2<f32>) -> bool {
+    (0..a.len()).any(|i| line_line(a[i], a[(i + 1) % a.len()], start, end))
+}
+
+pub fn check_line_of_sight(start: Vector2<f32>, end: Vector2<f32>, collisions: &[Polygon]) -> bool {
+    !collisions.iter().any(|collision| {
+        let center = collision.center();
+        line_circle(start, end, center, collision.radius(center)) && check_collision_line(collision, start, end)
+    })
+}
+
 pub fn check_collision_array(position: Vector3<f32>, collider: &Polygon, collisions: &[Polygon]) -> bool {
     for collision in collisions.iter() {
         let result = check_collision(collider, collision, vec2(position.x, position.z));
@@ -0,0 +1,279 @@
+use super::*;
+use std::f32::consts::PI;
+
+/// Largest slice of an arc tested as one triangle, keeps the chord close to the curve.
+const ARC_SLICE: f32 = 0.5;
+
+/// Area covered by a melee attack, relative to the attacker's position and facing.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HitShape {
+    /// Circle of `radius` placed `distance` ahead.
+    Circle { distance: f32, radius: f32 },
+    /// Sector of `radius` around the attacker, `angle` radians wide and centered on the facing.
+    Arc { radius: f32, angle: f32 },
+    /// Thrust `length` ahead and `width` across.
+    Line { length: f32, width: f32 },
+}
+
+pub fn check_collision_shape(a: &Polygon, shape: HitShape, origin: Vector2<f32>, direction: Vector2<f32>) -> bool {
+    if direction.is_zero() {
+        return false;
+    }
+
+    let direction = direction.normalize();
+
+    match shape {
+        HitShape::Circle { distance, radius } => check_collision_circle(a, origin + direction * distance, radius),
+        HitShape::Arc { radius, angle } => {
+            if angle <= 0.0 || radius <= 0.0 {
+                return false;
+            }
+
+            let angle = angle.min(PI * 2.0);
+            let slices = (angle / ARC_SLICE).ceil() as usize;
+            let step = angle / slices as f32;
+            let start = direction.y.atan2(direction.x) - angle / 2.0;
+
+            (0..slices).any(|i| {
+                let from = start + step * i as f32;
+                let to = from + step;
+                let slice = vec![
+                    origin,
+                    origin + vec2(from.cos(), from.sin()) * radius,
+                    origin + vec2(to.cos(), to.sin()) * radius,
+                ];
+
+                check_collision(a, &slice, Vector2::zero()) != Intersection::None
+            })
+        }
+        HitShape::Line { length, width } => {
+            if length <= 0.0 || width <= 0.0 {
+                return false;
+            }
+
+            let side = vec2(-direction.y, direction.x) * width / 2.0;
+            let end = origin + direction * length;
+            let rectangle = vec![origin - side, end - side, end + side, origin + side];
+
+            check_collision(a, &rectangle, Vector2::zero()) != Intersection::None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORWARD: Vector2<f32> = Vector2::new(1.0, 0.0);
+
+    fn square(center: Vector2<f32>, half: f32) -> Polygon {
+        vec![
+            center + vec2(-half, -half),
+            center + vec2(half, -half),
+            center + vec2(half, half),
+            center + vec2(-half, half),
+        ]
+    }
+
+    fn at_angle(degrees: f32, distance: f32) -> Vector2<f32> {
+        let angle = degrees.to_radians();
+        vec2(angle.cos(), angle.sin()) * distance
+    }
+
+    #[test]
+    fn arc_hits_inside_its_width() {
+        let arc = HitShape::Arc {
+            radius: 2.0,
+            angle: 90f32.to_radians(),
+        };
+
+        for degrees in [-40.0, 0.0, 40.0] {
+            assert!(check_collision_shape(
+                &square(at_angle(degrees, 1.5), 0.05),
+                arc,
+                Vector2::zero(),
+                FORWARD
+            ));
+        }
+    }
+
+    #[test]
+    fn arc_misses_just_outside_its_width() {
+        let arc = HitShape::Arc {
+            radius: 2.0,
+            angle: 90f32.to_radians(),
+        };
+
+        for degrees in [-50.0, 50.0, 180.0] {
+            assert!(!check_collision_shape(
+                &square(at_angle(degrees, 1.5), 0.05),
+                arc,
+                Vector2::zero(),
+                FORWARD
+            ));
+        }
+    }
+
+    #[test]
+    fn arc_misses_beyond_its_radius() {
+        let arc = HitShape::Arc {
+            radius: 2.0,
+            angle: 90f32.to_radians(),
+        };
+
+        assert!(!check_collision_shape(
+            &square(at_angle(0.0, 2.2), 0.1),
+            arc,
+            Vector2::zero(),
+            FORWARD
+        ));
+    }
+
+    #[test]
+    fn arc_hits_a_target_straddling_its_edge() {
+        let arc = HitShape::Arc {
+            radius: 2.0,
+            angle: 90f32.to_radians(),
+        };
+
+        assert!(check_collision_shape(
+            &square(at_angle(45.0, 1.5), 0.3),
+            arc,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(check_collision_shape(
+            &square(at_angle(-45.0, 1.5), 0.3),
+            arc,
+            Vector2::zero(),
+            FORWARD
+        ));
+    }
+
+    #[test]
+    fn arc_follows_the_direction() {
+        let arc = HitShape::Arc {
+            radius: 2.0,
+            angle: 90f32.to_radians(),
+        };
+        let target = square(vec2(0.0, 1.5), 0.05);
+
+        assert!(check_collision_shape(&target, arc, Vector2::zero(), vec2(0.0, 3.0)));
+        assert!(!check_collision_shape(&target, arc, Vector2::zero(), vec2(0.0, -3.0)));
+    }
+
+    #[test]
+    fn line_is_limited_by_length() {
+        let line = HitShape::Line { length: 3.0, width: 1.0 };
+
+        assert!(check_collision_shape(
+            &square(vec2(2.5, 0.0), 0.1),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(!check_collision_shape(
+            &square(vec2(3.5, 0.0), 0.1),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(!check_collision_shape(
+            &square(vec2(-1.0, 0.0), 0.1),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+    }
+
+    #[test]
+    fn line_is_limited_by_width() {
+        let line = HitShape::Line { length: 3.0, width: 1.0 };
+
+        assert!(check_collision_shape(
+            &square(vec2(1.5, 0.4), 0.05),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(check_collision_shape(
+            &square(vec2(1.5, -0.4), 0.05),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(!check_collision_shape(
+            &square(vec2(1.5, 0.7), 0.05),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(!check_collision_shape(
+            &square(vec2(1.5, -0.7), 0.05),
+            line,
+            Vector2::zero(),
+            FORWARD
+        ));
+    }
+
+    #[test]
+    fn circle_is_placed_ahead() {
+        let circle = HitShape::Circle {
+            distance: 1.0,
+            radius: 0.5,
+        };
+
+        assert!(check_collision_shape(
+            &square(vec2(1.4, 0.0), 0.05),
+            circle,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(!check_collision_shape(
+            &square(vec2(1.7, 0.0), 0.05),
+            circle,
+            Vector2::zero(),
+            FORWARD
+        ));
+        assert!(!check_collision_shape(
+            &square(vec2(-1.0, 0.0), 0.05),
+            circle,
+            Vector2::zero(),
+            FORWARD
+        ));
+    }
+
+    #[test]
+    fn shapes_are_placed_at_the_origin() {
+        let origin = vec2(10.0, -5.0);
+        let line = HitShape::Line { length: 3.0, width: 1.0 };
+
+        assert!(check_collision_shape(
+            &square(origin + vec2(2.5, 0.0), 0.1),
+            line,
+            origin,
+            FORWARD
+        ));
+        assert!(!check_collision_shape(&square(vec2(2.5, 0.0), 0.1), line, origin, FORWARD));
+    }
+
+    #[test]
+    fn zero_direction_hits_nothing() {
+        let target = square(Vector2::zero(), 1.0);
+        let shapes = [
+            HitShape::Circle {
+                distance: 0.0,
+                radius: 1.0,
+            },
+            HitShape::Arc {
+                radius: 2.0,
+                angle: PI * 2.0,
+            },
+            HitShape::Line { length: 2.0, width: 2.0 },
+        ];
+
+        for shape in shapes {
+            assert!(!check_collision_shape(&target, shape, Vector2::zero(), Vector2::zero()));
+        }
+    }
+}
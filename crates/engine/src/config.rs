@@ -4,7 +4,6 @@ pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 pub const CLEAR_COLOR: wgpu::Color = wgpu::Color::BLACK;
 pub const MAX_JOINT_COUNT: usize = 48;
 pub const UPDATES_PER_SECOND: f32 = 50.0;
-pub const ANIMATION_BLEND_SECONDS: f32 = 0.25;
 pub const JOYSTICK_RADIUS: f32 = 0.12;
 pub const CAMERA_ROTATION: f32 = 45.0;
 pub const Z_FAR: f32 = 25.0;
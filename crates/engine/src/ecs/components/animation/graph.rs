@@ -0,0 +1,147 @@
+use crate::file;
+use fxhash::FxHashMap;
+use serde_derive::Deserialize;
+
+/// A value the game feeds into the animation graph, e.g. the velocity or the current action.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnimationParameter {
+    Number(f32),
+    Text(String),
+}
+
+impl From<f32> for AnimationParameter {
+    fn from(value: f32) -> Self {
+        AnimationParameter::Number(value)
+    }
+}
+
+impl From<&str> for AnimationParameter {
+    fn from(value: &str) -> Self {
+        AnimationParameter::Text(value.to_string())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AnimationSource {
+    Name(String),
+    /// The animation named by a text parameter, such as the current combo step.
+    Parameter {
+        parameter: String,
+    },
+}
+
+/// Plays the animation at a number parameter times `scale`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SpeedBinding {
+    pub parameter: String,
+    pub scale: f32,
+}
+
+/// Without a speed or length the animation plays at the speed it was made with.
+/// A state without an animation fades its layer out.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnimationState {
+    pub animation: Option<AnimationSource>,
+    #[serde(default)]
+    pub repeat: bool,
+    pub speed: Option<SpeedBinding>,
+    /// Number parameter with the seconds the animation is stretched to last.
+    pub length: Option<String>,
+}
+
+/// Holds when the parameter equals the text or is one of the listed ones, or a number lies above and below the given bounds.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnimationCondition {
+    pub parameter: String,
+    pub equals: Option<String>,
+    #[serde(default)]
+    pub one_of: Vec<String>,
+    pub above: Option<f32>,
+    pub below: Option<f32>,
+}
+
+impl AnimationCondition {
+    fn check(&self, parameters: &FxHashMap<String, AnimationParameter>) -> bool {
+        match parameters.get(&self.parameter) {
+            Some(AnimationParameter::Text(text)) => self.equals.as_ref().is_some_and(|equals| equals == text) || self.one_of.contains(text),
+            Some(AnimationParameter::Number(number)) => {
+                self.above.is_none_or(|above| *number > above) && self.below.is_none_or(|below| *number < below)
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnimationTransition {
+    /// States the transition can be taken from, any state when empty.
+    #[serde(default)]
+    pub from: Vec<String>,
+    pub to: String,
+    #[serde(default)]
+    pub when: Vec<AnimationCondition>,
+    /// Seconds to blend into the new state, the graph's default when not set.
+    pub blend: Option<f32>,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+/// States and the transitions between them that drive one channel.
+/// Transitions are checked in order and the first one that applies is taken.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnimationLayer {
+    pub channel: String,
+    /// Joint whose subtree the layer animates, the whole skeleton when not set.
+    pub mask: Option<String>,
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    /// Adds the difference from the rest pose on top of the layers below instead of replacing them.
+    #[serde(default)]
+    pub additive: bool,
+    pub initial: String,
+    pub blend: f32,
+    pub states: FxHashMap<String, AnimationState>,
+    pub transitions: Vec<AnimationTransition>,
+}
+
+/// The animation graph of one model, loaded from `assets/animations`.
+/// The base layer lives at the top level, `layers` are applied over it in order.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnimationGraph {
+    #[serde(flatten)]
+    pub base: AnimationLayer,
+    #[serde(default)]
+    pub layers: Vec<AnimationLayer>,
+}
+
+impl AnimationGraph {
+    pub fn load(path: &str) -> Self {
+        match serde_json::from_str::<AnimationGraph>(file::read_string(path).as_str()) {
+            Ok(graph) => graph,
+            Err(err) => panic!("{}: {}", path, err),
+        }
+    }
+
+    pub fn layers(&self) -> impl Iterator<Item = &AnimationLayer> {
+        std::iter::once(&self.base).chain(self.layers.iter())
+    }
+}
+
+impl AnimationLayer {
+    pub fn get_state(&self, name: &str) -> &AnimationState {
+        self.states
+            .get(name)
+            .unwrap_or_else(|| panic!("Could not find animation state: {}", name))
+    }
+
+    pub(super) fn next_transition(&self, state: &str, parameters: &FxHashMap<String, AnimationParameter>) -> Option<&AnimationTransition> {
+        self.transitions.iter().find(|transition| {
+            transition.to != state
+                && (transition.from.is_empty() || transition.from.iter().any(|from| from == state))
+                && transition.when.iter().all(|condition| condition.check(parameters))
+        })
+    }
+}
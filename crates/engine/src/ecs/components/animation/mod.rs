@@ -0,0 +1,265 @@
+use bevy_ecs::prelude::*;
+use fxhash::FxHashMap;
+use std::{sync::Arc, time::Instant};
+mod graph;
+pub use graph::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnimationSpeed {
+    Original,
+    Length(f32),
+    Speed(f32),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnimationStatus {
+    Default,
+    Repeat,
+    Stopped,
+}
+
+#[derive(Clone, Debug)]
+pub struct Animation {
+    pub name: String,
+    pub elapsed: f32,
+    pub started: Instant,
+    pub speed: AnimationSpeed,
+    pub status: AnimationStatus,
+    /// Seconds it takes to blend in over the animation before it.
+    pub blend: f32,
+}
+
+#[derive(Debug)]
+pub struct Channel {
+    pub queue: Vec<Animation>,
+    /// Joint whose subtree the channel animates, the whole skeleton when not set or not in the model.
+    pub mask: Option<String>,
+    /// How much of the channel shows over the channels below it.
+    pub weight: f32,
+    /// Adds the difference from the rest pose instead of replacing the pose below.
+    pub additive: bool,
+    /// Channels are applied from the lowest layer up.
+    pub layer: usize,
+    target_weight: f32,
+    fade: f32,
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Self {
+            queue: vec![],
+            mask: None,
+            weight: 1.0,
+            additive: false,
+            layer: 0,
+            target_weight: 1.0,
+            fade: 0.0,
+        }
+    }
+}
+
+impl Channel {
+    /// Moves the weight to `weight` over `seconds`, right away when there is no time to fade.
+    pub fn fade_to(&mut self, weight: f32, seconds: f32) {
+        self.target_weight = weight;
+        self.fade = seconds;
+        if seconds <= 0.0 {
+            self.weight = weight;
+        }
+    }
+
+    /// Steps the weight towards its target, a channel that has faded out lets go of its animations.
+    pub fn update_weight(&mut self, delta: f32) {
+        if self.fade <= 0.0 {
+            self.weight = self.target_weight;
+        } else {
+            let step = delta / self.fade;
+            self.weight = if self.weight < self.target_weight {
+                (self.weight + step).min(self.target_weight)
+            } else {
+                (self.weight - step).max(self.target_weight)
+            };
+        }
+
+        if self.weight <= 0.0 && self.target_weight <= 0.0 {
+            self.queue.clear();
+        }
+    }
+
+    pub fn get_blend_factor(&self, index: usize) -> f32 {
+        if let Some(animation) = self.queue.get(index) {
+            if animation.blend <= 0.0 {
+                return 1.0;
+            }
+
+            let mut elapsed = animation.started.elapsed().as_secs_f32();
+
+            if let Some(next_animation) = self.queue.get(index + 1) {
+                elapsed -= next_animation.started.elapsed().as_secs_f32();
+            }
+
+            return (elapsed / animation.blend).min(1.0);
+        }
+
+        1.0
+    }
+}
+
+#[derive(Component)]
+pub struct Animations {
+    pub channels: FxHashMap<String, Channel>,
+    /// Holds every channel on its current pose, used for hit-stop.
+    pub frozen: bool,
+    graph: Option<Arc<AnimationGraph>>,
+    /// Current state of each layer in the graph.
+    pub states: Vec<String>,
+    parameters: FxHashMap<String, AnimationParameter>,
+}
+
+impl Animations {
+    pub fn new(key: &str, animation: &str, status: AnimationStatus) -> Self {
+        let mut channels = FxHashMap::default();
+
+        channels.insert(
+            key.to_string(),
+            Channel {
+                queue: vec![Animation {
+                    name: animation.to_string(),
+                    elapsed: 0.0,
+                    started: Instant::now(),
+                    speed: AnimationSpeed::Original,
+                    status,
+                    blend: 0.0,
+                }],
+                ..Default::default()
+            },
+        );
+
+        Self {
+            channels,
+            frozen: false,
+            graph: None,
+            states: vec![],
+            parameters: FxHashMap::default(),
+        }
+    }
+
+    /// Starts every layer in its initial state, the graph picks the animations from then on.
+    pub fn from_graph(graph: Arc<AnimationGraph>) -> Self {
+        let mut animations = Self {
+            channels: FxHashMap::default(),
+            frozen: false,
+            states: graph.layers().map(|layer| layer.initial.clone()).collect(),
+            graph: Some(graph.clone()),
+            parameters: FxHashMap::default(),
+        };
+
+        for (index, layer) in graph.layers().enumerate() {
+            let weight = if layer.get_state(&layer.initial).animation.is_some() {
+                layer.weight
+            } else {
+                0.0
+            };
+
+            animations.set_layer(&layer.channel, index, layer.mask.as_deref(), layer.additive);
+            animations.fade_channel(&layer.channel, weight, 0.0);
+        }
+
+        animations.update_graph();
+        animations
+    }
+
+    /// Sets where the channel sits in the layers and which part of the skeleton it animates.
+    pub fn set_layer(&mut self, channel: &str, layer: usize, mask: Option<&str>, additive: bool) {
+        let channel = self.channels.entry(channel.to_string()).or_default();
+        channel.layer = layer;
+        channel.mask = mask.map(|mask| mask.to_string());
+        channel.additive = additive;
+    }
+
+    pub fn fade_channel(&mut self, channel: &str, weight: f32, seconds: f32) {
+        self.channels.entry(channel.to_string()).or_default().fade_to(weight, seconds);
+    }
+
+    pub fn set_parameter(&mut self, name: &str, value: impl Into<AnimationParameter>) {
+        self.parameters.insert(name.to_string(), value.into());
+    }
+
+    /// Takes the first transition that applies in each layer and keeps the speed of the current state bound to its parameter.
+    pub fn update_graph(&mut self) {
+        let graph = match &self.graph {
+            Some(graph) => graph.clone(),
+            None => return,
+        };
+
+        for (index, layer) in graph.layers().enumerate() {
+            self.update_layer(index, layer);
+        }
+    }
+
+    fn update_layer(&mut self, index: usize, layer: &AnimationLayer) {
+        let mut blend = layer.blend;
+        if let Some(transition) = layer.next_transition(&self.states[index], &self.parameters) {
+            self.states[index] = transition.to.clone();
+            blend = transition.blend.unwrap_or(layer.blend);
+
+            // Layers fade out in states without an animation
+            let weight = if layer.get_state(&transition.to).animation.is_some() {
+                layer.weight
+            } else {
+                0.0
+            };
+            self.fade_channel(&layer.channel, weight, blend);
+        }
+
+        let state = layer.get_state(&self.states[index]);
+        let name = match &state.animation {
+            None => return,
+            Some(AnimationSource::Name(name)) => name.as_str(),
+            Some(AnimationSource::Parameter { parameter }) => match self.parameters.get(parameter) {
+                Some(AnimationParameter::Text(name)) => name.as_str(),
+                _ => return,
+            },
+        };
+
+        let number = |parameter: &str| match self.parameters.get(parameter) {
+            Some(AnimationParameter::Number(number)) => *number,
+            _ => 0.0,
+        };
+
+        let speed = match (&state.speed, &state.length) {
+            (Some(speed), _) => AnimationSpeed::Speed(number(&speed.parameter) * speed.scale),
+            (None, Some(length)) => AnimationSpeed::Length(number(length)),
+            (None, None) => AnimationSpeed::Original,
+        };
+
+        let status = if state.repeat {
+            AnimationStatus::Repeat
+        } else {
+            AnimationStatus::Default
+        };
+
+        let name = name.to_string();
+        self.set_animation(&layer.channel, &name, speed, status, blend);
+    }
+
+    pub fn set_animation(&mut self, channel: &str, animation: &str, speed: AnimationSpeed, run: AnimationStatus, blend: f32) {
+        let channel = self.channels.entry(channel.to_string()).or_default();
+        if let Some(last) = channel.queue.last_mut() {
+            if last.name == animation {
+                last.speed = speed;
+                return;
+            }
+        }
+
+        channel.queue.push(Animation {
+            name: animation.to_string(),
+            speed,
+            elapsed: 0.0,
+            started: Instant::now(),
+            status: run,
+            // Nothing to blend from yet
+            blend: if channel.queue.is_empty() { 0.0 } else { blend },
+        });
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{model::animation::SoundEffect, ModelInstance};
+use crate::{
+    model::animation::{EventKeyframe, SoundEffect},
+    ModelInstance,
+};
 use bevy_ecs::prelude::*;
 use fxhash::FxHashMap;
 
@@ -7,9 +10,26 @@ pub struct Model {
     pub key: String,
     pub animation_times: FxHashMap<String, f32>,
     pub animation_sound_effects: FxHashMap<String, SoundEffect>,
+    pub animation_events: FxHashMap<String, Vec<EventKeyframe>>,
 }
 
 impl Model {
+    /// A model without any animations, for models that are only drawn.
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.into(),
+            animation_times: FxHashMap::default(),
+            animation_sound_effects: FxHashMap::default(),
+            animation_events: FxHashMap::default(),
+        }
+    }
+
+    pub fn has_animation_event(&self, animation: &str, event: &str) -> bool {
+        self.animation_events
+            .get(animation)
+            .is_some_and(|events| events.iter().any(|e| e.name == event))
+    }
+
     pub fn get_model<'a>(&'a self, ctx: &'a crate::Context) -> &'a ModelInstance {
         ctx.model_instances
             .get(&self.key)
@@ -0,0 +1,8 @@
+use bevy_ecs::prelude::*;
+
+/// Sent when a playing animation passes one of its event keyframes, e.g. `hit` or `footstep`.
+pub struct AnimationEvent {
+    pub entity: Entity,
+    pub animation: String,
+    pub name: String,
+}
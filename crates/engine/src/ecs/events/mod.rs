@@ -0,0 +1,3 @@
+mod animation;
+
+pub use animation::AnimationEvent;
@@ -1,23 +1,31 @@
-use crate::{
-    config,
-    ecs::{
-        components::{self, Sound},
-        resources,
-    },
+use crate::ecs::{
+    components::{self, Sound},
+    events, resources,
 };
 use bevy_ecs::prelude::*;
 
 pub fn animation(
     time: Res<resources::Time>,
+    mut animation_events: EventWriter<events::AnimationEvent>,
     mut query: Query<(
+        Entity,
         &mut components::Animations,
         &components::Model,
         Option<&mut components::SoundEffects>,
     )>,
 ) {
-    for (mut animation, model, mut sound_effects) in query.iter_mut() {
+    for (entity, mut animation, model, mut sound_effects) in query.iter_mut() {
+        if animation.frozen {
+            continue;
+        }
+
+        animation.update_graph();
+
         for (_, channel) in animation.channels.iter_mut() {
-            for animation in channel.queue.iter_mut() {
+            channel.update_weight(time.last_frame);
+            let current = channel.queue.len().saturating_sub(1);
+
+            for (index, animation) in channel.queue.iter_mut().enumerate() {
                 let total_time = *model
                     .animation_times
                     .get(&animation.name)
@@ -39,6 +47,19 @@ pub fn animation(
                     }
                 }
 
+                // Animations that are blending out have already had their turn
+                if index == current {
+                    if let Some(events) = model.animation_events.get(&animation.name) {
+                        for event in events.iter().filter(|e| passed(e.timestamp, animation.elapsed, new_elapsed)) {
+                            animation_events.send(events::AnimationEvent {
+                                entity,
+                                animation: animation.name.clone(),
+                                name: event.name.clone(),
+                            });
+                        }
+                    }
+                }
+
                 animation.elapsed = new_elapsed;
             }
 
@@ -47,6 +68,15 @@ pub fn animation(
     }
 }
 
+/// Whether `timestamp` lies in the step from `from` to `to`, repeating animations wrap around to the start.
+fn passed(timestamp: f32, from: f32, to: f32) -> bool {
+    if to >= from {
+        timestamp > from && timestamp <= to
+    } else {
+        timestamp > from || timestamp <= to
+    }
+}
+
 fn animate_channel(animation: &mut components::Animation, total_time: f32, last_frame: f32) -> f32 {
     let speed = match animation.speed {
         components::AnimationSpeed::Original => 1.0,
@@ -77,7 +107,7 @@ fn cleanup_channel(channel: &mut components::Channel) {
         .enumerate()
         .filter(|(index, _)| {
             if let Some(next) = channel.queue.get(index + 1) {
-                next.started.elapsed().as_secs_f32() < config::ANIMATION_BLEND_SECONDS
+                next.started.elapsed().as_secs_f32() < next.blend
             } else {
                 true
             }
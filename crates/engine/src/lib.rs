@@ -224,6 +224,12 @@ impl Engine {
             .filter(|(_, b)| b.sound_effect.is_some())
             .map(|(a, b)| (a.clone(), b.sound_effect.clone().unwrap()))
             .collect();
+        let animation_events = nodes
+            .animations
+            .iter()
+            .filter(|(_, b)| !b.events.is_empty())
+            .map(|(a, b)| (a.clone(), b.events.clone()))
+            .collect();
 
         let key = uuid::Uuid::new_v4().to_string();
 
@@ -240,6 +246,7 @@ impl Engine {
             key,
             animation_times,
             animation_sound_effects,
+            animation_events,
         }
     }
 
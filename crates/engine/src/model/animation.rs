@@ -67,9 +67,17 @@ impl<T: Interpolate> Channel<T> {
 }
 
 #[derive(Clone, Debug, serde_derive::Deserialize)]
-pub struct RawSoundEffect {
-    sound_effect: String,
+struct RawEventKeyframe {
+    name: String,
+    keyframe: usize,
+}
+
+#[derive(Clone, Debug, Default, serde_derive::Deserialize)]
+#[serde(default)]
+struct RawExtras {
+    sound_effect: Option<String>,
     sound_effect_keyframes: Vec<usize>,
+    events: Vec<RawEventKeyframe>,
 }
 
 #[derive(Clone, Debug)]
@@ -78,6 +86,13 @@ pub struct SoundEffect {
     pub timestamps: Vec<f32>,
 }
 
+/// A named point in an animation that gameplay can react to.
+#[derive(Clone, Debug)]
+pub struct EventKeyframe {
+    pub name: String,
+    pub timestamp: f32,
+}
+
 #[derive(Clone, Debug)]
 pub struct Animation {
     translation_channels: Vec<Channel<Vector3<f32>>>,
@@ -85,6 +100,7 @@ pub struct Animation {
     scale_channels: Vec<Channel<Vector3<f32>>>,
     pub total_time: f32,
     pub sound_effect: Option<SoundEffect>,
+    pub events: Vec<EventKeyframe>,
 }
 
 impl Animation {
@@ -114,19 +130,30 @@ impl Animation {
         // TODO: This is not very safe, total keyframes can be more
         let keyframes = translation_channels.iter().map(|c| c.sampler.times.len()).max().unwrap_or(0);
 
-        let sound_effect = if let Some(json) = animation.extras() {
-            let raw: RawSoundEffect = serde_json::from_str(json.get()).unwrap();
-            Some(SoundEffect {
-                name: raw.sound_effect,
-                timestamps: raw
-                    .sound_effect_keyframes
-                    .into_iter()
-                    .map(|keyframe| (keyframe as f32 / keyframes as f32) * total_time)
-                    .collect(),
+        let extras: RawExtras = animation
+            .extras()
+            .as_ref()
+            .map(|json| serde_json::from_str(json.get()).unwrap())
+            .unwrap_or_default();
+        let keyframe_time = |keyframe: usize| (keyframe as f32 / keyframes as f32) * total_time;
+
+        let sound_effect = extras.sound_effect.map(|name| SoundEffect {
+            name,
+            timestamps: extras
+                .sound_effect_keyframes
+                .iter()
+                .map(|keyframe| keyframe_time(*keyframe))
+                .collect(),
+        });
+
+        let events = extras
+            .events
+            .into_iter()
+            .map(|event| EventKeyframe {
+                name: event.name,
+                timestamp: keyframe_time(event.keyframe),
             })
-        } else {
-            None
-        };
+            .collect();
 
         Self {
             translation_channels,
@@ -134,26 +161,55 @@ impl Animation {
             scale_channels,
             total_time,
             sound_effect,
+            events,
         }
     }
 
-    pub fn animate_nodes(&self, nodes: &mut [node::Node], time: f32, blend_factor: f32) -> bool {
+    /// Moves the nodes towards the pose at `time`, only the ones in the mask when there is one.
+    pub fn animate_nodes(&self, nodes: &mut [node::Node], time: f32, blend_factor: f32, mask: Option<&[bool]>) -> bool {
         let NodesKeyFrame(translations, rotations, scale) = self.sample(time);
+        let masked = |node_index: &usize| mask.is_none_or(|mask| mask[*node_index]);
 
-        translations.iter().for_each(|(node_index, translation)| {
-            nodes[*node_index].local_transform.translation =
-                nodes[*node_index].local_transform.translation.lerp(*translation, blend_factor);
-        });
-        rotations.iter().for_each(|(node_index, rotation)| {
+        translations
+            .iter()
+            .filter(|(i, _)| masked(i))
+            .for_each(|(node_index, translation)| {
+                nodes[*node_index].local_transform.translation =
+                    nodes[*node_index].local_transform.translation.lerp(*translation, blend_factor);
+            });
+        rotations.iter().filter(|(i, _)| masked(i)).for_each(|(node_index, rotation)| {
             nodes[*node_index].local_transform.rotation = nodes[*node_index].local_transform.rotation.slerp(*rotation, blend_factor);
         });
-        scale.iter().for_each(|(node_index, scale)| {
+        scale.iter().filter(|(i, _)| masked(i)).for_each(|(node_index, scale)| {
             nodes[*node_index].local_transform.scale = nodes[*node_index].local_transform.scale.lerp(*scale, blend_factor);
         });
 
         !translations.is_empty() || !rotations.is_empty() || !scale.is_empty()
     }
 
+    /// Adds how far the pose at `time` is from the rest pose on top of the nodes, scaled by `weight`.
+    pub fn add_to_nodes(&self, nodes: &mut [node::Node], rest: &[node::Node], time: f32, weight: f32, mask: Option<&[bool]>) {
+        let NodesKeyFrame(translations, rotations, scale) = self.sample(time);
+        let masked = |node_index: &usize| mask.is_none_or(|mask| mask[*node_index]);
+
+        translations
+            .iter()
+            .filter(|(i, _)| masked(i))
+            .for_each(|(node_index, translation)| {
+                let delta = translation - rest[*node_index].local_transform.translation;
+                nodes[*node_index].local_transform.translation += delta * weight;
+            });
+        rotations.iter().filter(|(i, _)| masked(i)).for_each(|(node_index, rotation)| {
+            let delta = rest[*node_index].local_transform.rotation.conjugate() * rotation;
+            let rotation = &mut nodes[*node_index].local_transform.rotation;
+            *rotation = *rotation * Quaternion::one().slerp(delta, weight);
+        });
+        scale.iter().filter(|(i, _)| masked(i)).for_each(|(node_index, scale)| {
+            let delta = scale - rest[*node_index].local_transform.scale;
+            nodes[*node_index].local_transform.scale += delta * weight;
+        });
+    }
+
     fn sample(&self, t: f32) -> NodesKeyFrame {
         NodesKeyFrame(
             self.translation_channels.iter().filter_map(|tc| tc.sample(t)).collect::<Vec<_>>(),
@@ -26,6 +26,23 @@ pub struct GltfModelNodes {
     pub nodes: Vec<node::Node>,
     pub skins: Vec<skin::Skin>,
     pub animations: FxHashMap<String, animation::Animation>,
+    /// Masks of the animation layers by joint name, built once by `GltfModel::add_bone_masks`.
+    pub bone_masks: FxHashMap<String, Vec<bool>>,
+}
+
+impl GltfModelNodes {
+    /// Marks the node with the given name and every node below it, `None` when the model has no such node.
+    fn build_bone_mask(&self, name: &str) -> Option<Vec<bool>> {
+        let root = self.nodes.iter().position(|node| node.name.as_deref() == Some(name))?;
+        let mut mask = vec![false; self.nodes.len()];
+
+        // Parents come before their children in the traversal
+        for (index, parent_index) in &self.depth_first_taversal_indices {
+            mask[*index] = *index == root || parent_index.is_some_and(|parent| mask[parent]);
+        }
+
+        Some(mask)
+    }
 }
 
 pub struct GltfModel {
@@ -120,6 +137,7 @@ impl GltfModel {
                 depth_first_taversal_indices,
                 nodes,
                 skins,
+                bone_masks: FxHashMap::default(),
             },
         }
     }
@@ -147,6 +165,19 @@ impl GltfModel {
             .collect()
     }
 
+    /// Builds the masks for the given joints, has to be called before the model is initialized. Layers masking a joint
+    /// the model does not have animate the whole skeleton.
+    pub fn add_bone_masks<'a>(&mut self, joints: impl IntoIterator<Item = &'a str>) {
+        for joint in joints {
+            match self.nodes.build_bone_mask(joint) {
+                Some(bone_mask) => {
+                    self.nodes.bone_masks.insert(joint.to_string(), bone_mask);
+                }
+                None => println!("Could not find joint \"{}\" to mask, animating the whole skeleton!", joint),
+            }
+        }
+    }
+
     pub fn get_sound_effects(&self) -> Vec<String> {
         self.nodes
             .animations
@@ -2,6 +2,7 @@ use cgmath::*;
 
 #[derive(Clone)]
 pub struct Node {
+    pub name: Option<String>,
     pub local_transform: super::Transform,
     pub global_transform_matrix: Matrix4<f32>,
     pub skin_index: Option<usize>,
@@ -22,6 +23,7 @@ impl Node {
         let children_indices = node.children().map(|c| c.index()).collect::<Vec<_>>();
 
         Self {
+            name: node.name().map(|name| name.to_string()),
             local_transform,
             global_transform_matrix,
             skin_index,
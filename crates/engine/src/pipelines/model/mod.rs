@@ -172,49 +172,63 @@ fn get_joint_transforms(model_nodes: &GltfModelNodes, animation: &Option<&compon
         let mut joint_transforms = vec![Matrix4::identity(); config::MAX_JOINT_COUNT];
         let mut nodes = model_nodes.nodes.clone();
 
-        animation.channels.iter().for_each(|(_, channel)| {
+        // Layers go on top of each other, each only moving the bones in its mask
+        let mut channels: Vec<&components::Channel> = animation.channels.values().filter(|c| c.weight > 0.01).collect();
+        channels.sort_by_key(|channel| channel.layer);
+
+        for channel in channels {
+            let mask = channel
+                .mask
+                .as_ref()
+                .and_then(|mask| model_nodes.bone_masks.get(mask))
+                .map(Vec::as_slice);
+
             for (index, animation) in channel.queue.iter().enumerate() {
-                let blend_factor = channel.get_blend_factor(index);
+                let blend_factor = channel.get_blend_factor(index) * channel.weight;
                 if blend_factor > 0.01 {
                     let cur_model_animation = model_nodes
                         .animations
                         .get(&animation.name)
                         .unwrap_or_else(|| panic!("Could not find animation: {}", &animation.name));
 
-                    cur_model_animation.animate_nodes(&mut nodes, animation.elapsed, blend_factor);
+                    if channel.additive {
+                        cur_model_animation.add_to_nodes(&mut nodes, &model_nodes.nodes, animation.elapsed, blend_factor, mask);
+                    } else {
+                        cur_model_animation.animate_nodes(&mut nodes, animation.elapsed, blend_factor, mask);
+                    }
                 }
             }
+        }
 
-            for (index, parent_index) in &model_nodes.depth_first_taversal_indices {
-                let parent_transform = parent_index
-                    .map(|id| {
-                        let parent = &nodes[id];
-                        parent.global_transform_matrix
-                    })
-                    .or_else(|| Some(Matrix4::identity()));
+        for (index, parent_index) in &model_nodes.depth_first_taversal_indices {
+            let parent_transform = parent_index
+                .map(|id| {
+                    let parent = &nodes[id];
+                    parent.global_transform_matrix
+                })
+                .or_else(|| Some(Matrix4::identity()));
 
-                let node = &mut nodes[*index];
-                node.apply_transform(parent_transform);
-            }
+            let node = &mut nodes[*index];
+            node.apply_transform(parent_transform);
+        }
 
-            for node in nodes.iter() {
-                let inverse_transform = node
-                    .global_transform_matrix
-                    .invert()
-                    .expect("Transform matrix should be invertible");
-
-                let skin_index = if let Some(skin_index) = node.skin_index { skin_index } else { 0 };
-
-                model_nodes.skins[skin_index]
-                    .joints
-                    .iter()
-                    .enumerate()
-                    .for_each(|(j_index, joint)| {
-                        joint_transforms[j_index] =
-                            inverse_transform * nodes[joint.node_id].global_transform_matrix * joint.inverse_bind_matrix;
-                    });
-            }
-        });
+        for node in nodes.iter() {
+            let inverse_transform = node
+                .global_transform_matrix
+                .invert()
+                .expect("Transform matrix should be invertible");
+
+            let skin_index = if let Some(skin_index) = node.skin_index { skin_index } else { 0 };
+
+            model_nodes.skins[skin_index]
+                .joints
+                .iter()
+                .enumerate()
+                .for_each(|(j_index, joint)| {
+                    joint_transforms[j_index] =
+                        inverse_transform * nodes[joint.node_id].global_transform_matrix * joint.inverse_bind_matrix;
+                });
+        }
 
         joint_transforms.iter().map(|jm| (*jm).into()).collect()
     } else {
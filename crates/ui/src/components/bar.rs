@@ -13,6 +13,7 @@ pub struct BarProps {
     pub border_color: Vector4<f32>,
     pub margin: Rect<Dimension>,
     pub width: Dimension,
+    pub height: Dimension,
 }
 
 impl Default for BarProps {
@@ -24,6 +25,7 @@ impl Default for BarProps {
             gradient: Default::default(),
             border_color: Vector4::new(0.0, 0.0, 0.0, 1.0),
             width: Default::default(),
+            height: Dimension::Auto,
             margin: Default::default(),
         }
     }
@@ -45,7 +47,7 @@ impl Bar {
                 margin: props.margin,
                 min_size: Size {
                     width: props.width,
-                    height: Dimension::Auto,
+                    height: props.height,
                 },
                 ..Default::default()
             },